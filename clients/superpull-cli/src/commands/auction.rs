@@ -0,0 +1,251 @@
+use anchor_client::solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+use anyhow::{bail, Result};
+use clap::Args;
+use serde_json::{json, Value};
+use superpull_client::{accounts, instructions, pda, quote, state::AuctionState};
+use superpull_program::{instruction::InitializeAuction, instructions::withdrawable_amount};
+
+use crate::{commands::now, context::Context, setup};
+
+#[derive(Args)]
+pub struct CreateAuctionArgs {
+    /// Mint of the payment token
+    #[arg(long)]
+    pub token_mint: Pubkey,
+    /// Price of the first item in base units
+    #[arg(long)]
+    pub base_price: u64,
+    /// Price increase per item in base units
+    #[arg(long)]
+    pub price_increment: u64,
+    #[arg(long)]
+    pub max_supply: u64,
+    /// Items to sell before the auction graduates
+    #[arg(long)]
+    pub minimum_items: u64,
+    /// Seconds from now until the deadline
+    #[arg(long, default_value_t = 24 * 60 * 60)]
+    pub duration: i64,
+    /// Items a single wallet can buy, 0 for unlimited
+    #[arg(long, default_value_t = 0)]
+    pub max_per_wallet: u64,
+    /// Share of referred bids paid to the referrer, in basis points
+    #[arg(long, default_value_t = 0)]
+    pub referral_bps: u16,
+    /// Seconds after graduation to confirm delivery, no guarantee when unset
+    #[arg(long)]
+    pub delivery_period: Option<i64>,
+    /// Wallet that can rule on disputes
+    #[arg(long)]
+    pub arbiter: Option<Pubkey>,
+    /// Wallet receiving the proceeds, defaults to the signer
+    #[arg(long)]
+    pub treasury: Option<Pubkey>,
+    /// Spread kept by the seller when an item is sold back, in basis points.
+    /// Sell-backs are disabled when unset
+    #[arg(long)]
+    pub sell_back_spread_bps: Option<u16>,
+    #[arg(long, default_value_t = 14)]
+    pub max_depth: u32,
+    #[arg(long, default_value_t = 64)]
+    pub max_buffer_size: u32,
+    #[arg(long, default_value = "SuperPull Collection")]
+    pub name: String,
+    #[arg(long, default_value = "SPULL")]
+    pub symbol: String,
+    #[arg(long, default_value = "https://assets.superpull.world/collection.json")]
+    pub uri: String,
+}
+
+/// Creates the tree, the collection and the escrow, then the auction itself
+/// with the signer as authority
+pub fn create(ctx: &Context, args: CreateAuctionArgs) -> Result<()> {
+    let authority = ctx.payer()?.pubkey();
+    let merkle_tree = Keypair::new();
+    let collection_mint = Keypair::new();
+
+    let auction_id = accounts::fetch_next_auction_id(&ctx.rpc, &authority)?;
+    let registry_pages = accounts::fetch_current_registry_pages(&ctx.rpc, &authority)?;
+    let (auction, _) = pda::find_auction(&authority, &collection_mint.pubkey(), auction_id);
+
+    ctx.log("Creating merkle tree...");
+    let tree_size = setup::merkle_tree_account_size(args.max_depth, args.max_buffer_size);
+    let tree_rent = ctx.rpc.get_minimum_balance_for_rent_exemption(tree_size)?;
+    ctx.send(
+        &setup::create_tree(
+            &authority,
+            &merkle_tree.pubkey(),
+            args.max_depth,
+            args.max_buffer_size,
+            tree_rent,
+        ),
+        &[&merkle_tree],
+    )?;
+
+    ctx.log("Creating collection...");
+    ctx.send(
+        &setup::create_collection(
+            &authority,
+            &collection_mint.pubkey(),
+            args.name,
+            args.symbol,
+            args.uri,
+        ),
+        &[&collection_mint],
+    )?;
+    ctx.send(
+        &setup::delegate_to_auction(
+            &authority,
+            &auction,
+            &collection_mint.pubkey(),
+            &merkle_tree.pubkey(),
+        ),
+        &[],
+    )?;
+
+    ctx.log("Initializing auction...");
+    let initialize = instructions::initialize_auction(
+        &authority,
+        &authority,
+        &merkle_tree.pubkey(),
+        &collection_mint.pubkey(),
+        &args.token_mint,
+        auction_id,
+        registry_pages,
+        InitializeAuction {
+            base_price: args.base_price,
+            price_increment: args.price_increment,
+            max_supply: args.max_supply,
+            minimum_items: args.minimum_items,
+            deadline: now() + args.duration,
+            max_per_wallet: args.max_per_wallet,
+            allowlist: None,
+            gate: None,
+            payees: Vec::new(),
+            dust_payee_index: 0,
+            referral_bps: args.referral_bps,
+            vesting: None,
+            delivery_period: args.delivery_period,
+            arbiter: args.arbiter,
+            treasury: args.treasury,
+            multisig_signers: Vec::new(),
+            multisig_threshold: 0,
+        },
+    );
+    let mut ixs = vec![
+        initialize,
+        setup::create_escrow(&authority, &auction, &args.token_mint),
+    ];
+    if args.sell_back_spread_bps.is_some() {
+        ixs.push(instructions::set_sell_back(&auction, &authority, args.sell_back_spread_bps));
+    }
+    let signature = ctx.send(&ixs, &[])?;
+
+    ctx.print(&json!({
+        "auction": auction.to_string(),
+        "auction_id": auction_id,
+        "merkle_tree": merkle_tree.pubkey().to_string(),
+        "collection_mint": collection_mint.pubkey().to_string(),
+        "escrow": pda::token_account(&auction, &args.token_mint).to_string(),
+        "signature": signature.to_string(),
+    }));
+    Ok(())
+}
+
+pub fn status(ctx: &Context, auction_address: &Pubkey) -> Result<()> {
+    let auction = accounts::fetch_auction(&ctx.rpc, auction_address)?;
+    let now = now();
+
+    let milestones = if auction.has_milestones {
+        accounts::fetch_milestones(&ctx.rpc, auction_address)?
+    } else {
+        None
+    };
+    let withdrawable = withdrawable_amount(&auction, milestones.as_ref(), now)?;
+
+    let mut status = auction_json(auction_address, &auction, now);
+    status["withdrawable"] = json!(withdrawable);
+    status["price"] = price_json(ctx, auction_address, &auction, now)?;
+    ctx.print(&status);
+    Ok(())
+}
+
+pub fn price(ctx: &Context, auction_address: &Pubkey) -> Result<()> {
+    let auction = accounts::fetch_auction(&ctx.rpc, auction_address)?;
+    ctx.print(&price_json(ctx, auction_address, &auction, now())?);
+    Ok(())
+}
+
+pub fn withdraw(ctx: &Context, auction_address: &Pubkey, amount: Option<u64>) -> Result<()> {
+    let auction = accounts::fetch_auction(&ctx.rpc, auction_address)?;
+    if auction.authority != ctx.payer()?.pubkey() {
+        bail!("signer is not the auction authority {}", auction.authority);
+    }
+    let config = accounts::fetch_config(&ctx.rpc)?;
+
+    let signature = ctx.send(
+        &[instructions::withdraw(
+            auction_address,
+            &auction,
+            &config.treasury,
+            &ctx.payer()?.pubkey(),
+            amount,
+        )],
+        &[],
+    )?;
+    ctx.print(&json!({ "signature": signature.to_string() }));
+    Ok(())
+}
+
+pub fn close(ctx: &Context, auction_address: &Pubkey) -> Result<()> {
+    let signature = ctx.send(
+        &[instructions::cancel_auction(auction_address, &ctx.payer()?.pubkey())],
+        &[],
+    )?;
+    ctx.print(&json!({ "signature": signature.to_string() }));
+    Ok(())
+}
+
+/// Quote of the next bid, or the reason the auction does not accept bids
+fn price_json(ctx: &Context, auction_address: &Pubkey, auction: &AuctionState, now: i64) -> Result<Value> {
+    let phases = if auction.has_phases {
+        accounts::fetch_phases(&ctx.rpc, auction_address)?
+    } else {
+        None
+    };
+    Ok(match quote::quote_bid(auction, phases.as_ref(), now, false) {
+        Ok(quote) => json!({
+            "price": quote.price,
+            "phase_index": quote.phase_index,
+            "allowlist_only": quote.allowlist_only,
+        }),
+        Err(err) => json!({ "error": err.to_string() }),
+    })
+}
+
+fn auction_json(address: &Pubkey, auction: &AuctionState, now: i64) -> Value {
+    json!({
+        "address": address.to_string(),
+        "auction_id": auction.auction_id,
+        "authority": auction.authority.to_string(),
+        "treasury": auction.payout_wallet().to_string(),
+        "merkle_tree": auction.merkle_tree.to_string(),
+        "collection_mint": auction.collection_mint.to_string(),
+        "token_mint": auction.token_mint.to_string(),
+        "base_price": auction.base_price,
+        "price_increment": auction.price_increment,
+        "current_supply": auction.current_supply,
+        "minimum_items": auction.minimum_items,
+        "max_supply": auction.max_supply,
+        "deadline": auction.deadline,
+        "total_value_locked": auction.total_value_locked,
+        "total_withdrawn": auction.total_withdrawn,
+        "is_graduated": auction.is_graduated,
+        "is_halted": auction.is_halted,
+        "sell_back_spread_bps": auction.sell_back_spread_bps,
+        "registry_status": auction.registry_status(now),
+    })
+}
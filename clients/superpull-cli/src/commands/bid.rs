@@ -0,0 +1,333 @@
+use anchor_client::{
+    solana_client::rpc_request::RpcRequest,
+    solana_sdk::{pubkey::Pubkey, signature::Signer},
+};
+use anyhow::{anyhow, bail, Result};
+use clap::Args;
+use serde_json::{json, Value};
+use std::{collections::HashMap, str::FromStr};
+use superpull_client::{accounts, instructions, pda, quote};
+use superpull_program::{
+    instructions::{AllowlistProof, ItemLeaf},
+    state::{AuctionState, MAX_REFUND_BOUNTY_LAMPORTS},
+};
+
+use crate::{commands::now, context::Context};
+
+#[derive(Args)]
+pub struct BidArgs {
+    pub auction: Pubkey,
+    /// Refuse to bid above this price, in base units
+    #[arg(long)]
+    pub max_price: Option<u64>,
+    /// Promoter who referred the bid
+    #[arg(long)]
+    pub referrer: Option<Pubkey>,
+    /// NFT proving a collection gate
+    #[arg(long)]
+    pub gate_nft: Option<Pubkey>,
+    /// Wallet receiving the item as a gift, refunds still go to the signer
+    #[arg(long)]
+    pub recipient: Option<Pubkey>,
+    /// Allowlist allocation of the signer
+    #[arg(long, requires = "proof")]
+    pub allocation: Option<u64>,
+    /// Allowlist proof as hex-encoded sibling hashes, from the leaf up
+    #[arg(long, num_args = 1.., requires = "allocation")]
+    pub proof: Vec<String>,
+}
+
+/// Bids for the next item at the quoted price
+pub fn bid(ctx: &Context, args: BidArgs) -> Result<()> {
+    let auction = accounts::fetch_auction(&ctx.rpc, &args.auction)?;
+    let phases = if auction.has_phases {
+        accounts::fetch_phases(&ctx.rpc, &args.auction)?
+    } else {
+        None
+    };
+
+    let quote = quote::quote_bid(&auction, phases.as_ref(), now(), args.referrer.is_some())
+        .map_err(|err| anyhow::anyhow!("auction does not accept bids: {err}"))?;
+    if let Some(max_price) = args.max_price {
+        if quote.price > max_price {
+            bail!("price {} is above the maximum of {max_price}", quote.price);
+        }
+    }
+
+    let allowlist_proof = match args.allocation {
+        Some(allocation) => Some(AllowlistProof {
+            allocation,
+            proof: args
+                .proof
+                .iter()
+                .map(|hash| parse_hash(hash))
+                .collect::<Result<_>>()?,
+        }),
+        None => None,
+    };
+
+    let bidder = ctx.payer()?.pubkey();
+    let signature = ctx.send(
+        &[instructions::place_bid(
+            &args.auction,
+            &auction,
+            &bidder,
+            &bidder,
+            args.recipient.as_ref(),
+            quote.price,
+            allowlist_proof,
+            args.referrer.as_ref(),
+            args.gate_nft.as_ref(),
+        )],
+        &[],
+    )?;
+
+    ctx.print(&json!({
+        "price": quote.price,
+        "referral_fee": quote.referral_fee,
+        "signature": signature.to_string(),
+    }));
+    Ok(())
+}
+
+pub fn list(ctx: &Context, auction: &Pubkey) -> Result<()> {
+    let bids = accounts::fetch_auction_bids(&ctx.rpc, auction)?;
+    let bids: Vec<Value> = bids
+        .iter()
+        .map(|(address, bid)| {
+            json!({
+                "address": address.to_string(),
+                "bidder": bid.bidder.to_string(),
+                "amount": bid.amount,
+                "count": bid.count,
+                "referral_fees": bid.referral_fees,
+            })
+        })
+        .collect();
+    ctx.print(&Value::Array(bids));
+    Ok(())
+}
+
+pub fn items(ctx: &Context, auction: &Pubkey) -> Result<()> {
+    let receipts = accounts::fetch_item_receipts(&ctx.rpc, auction, None)?;
+    let items: Vec<Value> = receipts
+        .iter()
+        .map(|(address, receipt)| {
+            json!({
+                "address": address.to_string(),
+                "serial": receipt.serial,
+                "bidder": receipt.bidder.to_string(),
+                "price": receipt.price,
+                "referral_fee": receipt.referral_fee,
+                "timestamp": receipt.timestamp,
+                "leaf_index": receipt.leaf_index,
+                "asset_id": receipt.asset_id.to_string(),
+            })
+        })
+        .collect();
+    ctx.print(&Value::Array(items));
+    Ok(())
+}
+
+/// Refunds the signer, closing the receipts of the refunded items
+pub fn refund(ctx: &Context, auction_address: &Pubkey) -> Result<()> {
+    let auction = accounts::fetch_auction(&ctx.rpc, auction_address)?;
+    let bidder = ctx.payer()?.pubkey();
+    let Some(bid) = accounts::fetch_bid(&ctx.rpc, auction_address, &bidder)? else {
+        bail!("no bid of {bidder} in auction {auction_address}");
+    };
+    let receipts: Vec<Pubkey> = accounts::fetch_item_receipts(&ctx.rpc, auction_address, Some(&bidder))?
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+    if receipts.len() as u64 != bid.open_receipts {
+        bail!(
+            "found {} of the {} open item receipts of {bidder}",
+            receipts.len(),
+            bid.open_receipts
+        );
+    }
+    let signature = ctx.send(
+        &[instructions::refund(auction_address, &auction, &bidder, &receipts)],
+        &[],
+    )?;
+    ctx.print(&json!({ "signature": signature.to_string() }));
+    Ok(())
+}
+
+/// Refunds the signer's items one by one through `refund_item`, burning
+/// their cNFTs. Only items still owned by the signer are refunded, the
+/// leaves and proofs come from the RPC's DAS API
+pub fn refund_item(ctx: &Context, auction_address: &Pubkey, bidder: Option<Pubkey>, serial: Option<u64>) -> Result<()> {
+    let auction = accounts::fetch_auction(&ctx.rpc, auction_address)?;
+    let owner = ctx.payer()?.pubkey();
+    let bidder = bidder.unwrap_or(owner);
+    let receipts: Vec<_> = accounts::fetch_item_receipts(&ctx.rpc, auction_address, Some(&bidder))?
+        .into_iter()
+        .filter(|(_, receipt)| serial.is_none_or(|serial| receipt.serial == serial))
+        .collect();
+    if receipts.is_empty() {
+        bail!("no open item receipts of {bidder} in auction {auction_address}");
+    }
+
+    let mut refunded = Vec::new();
+    let mut skipped = Vec::new();
+    let mut signatures = Vec::new();
+    for (_, receipt) in receipts {
+        let (leaf, asset_owner, proof) = fetch_item_leaf(ctx, &receipt.asset_id)?;
+        if asset_owner != owner {
+            ctx.log(&format!("item {} is owned by {asset_owner}, skipping", receipt.serial));
+            skipped.push(receipt.serial);
+            continue;
+        }
+        let signature = ctx.send(
+            &[instructions::refund_item(
+                auction_address,
+                &auction,
+                &bidder,
+                &owner,
+                receipt.serial,
+                receipt.referrer.as_ref(),
+                leaf,
+                &proof,
+            )],
+            &[],
+        )?;
+        signatures.push(signature.to_string());
+        refunded.push(receipt.serial);
+    }
+
+    ctx.print(&json!({
+        "refunded": refunded,
+        "skipped": skipped,
+        "signatures": signatures,
+    }));
+    Ok(())
+}
+
+/// Fetches the leaf, current owner and merkle proof of a cNFT from the DAS
+/// API
+fn fetch_item_leaf(ctx: &Context, asset_id: &Pubkey) -> Result<(ItemLeaf, Pubkey, Vec<Pubkey>)> {
+    let params = json!({ "id": asset_id.to_string() });
+    let asset: Value = ctx.rpc.send(RpcRequest::Custom { method: "getAsset" }, params.clone())?;
+    let asset_proof: Value = ctx.rpc.send(RpcRequest::Custom { method: "getAssetProof" }, params)?;
+
+    let leaf = ItemLeaf {
+        root: parse_base58(&asset_proof["root"])?.to_bytes(),
+        data_hash: parse_base58(&asset["compression"]["data_hash"])?.to_bytes(),
+        creator_hash: parse_base58(&asset["compression"]["creator_hash"])?.to_bytes(),
+    };
+    let owner = parse_base58(&asset["ownership"]["owner"])?;
+    let proof = asset_proof["proof"]
+        .as_array()
+        .ok_or_else(|| anyhow!("missing proof of asset {asset_id}"))?
+        .iter()
+        .map(parse_base58)
+        .collect::<Result<_>>()?;
+    Ok((leaf, owner, proof))
+}
+
+fn parse_base58(value: &Value) -> Result<Pubkey> {
+    let value = value.as_str().ok_or_else(|| anyhow!("missing field in DAS response"))?;
+    Ok(Pubkey::from_str(value)?)
+}
+
+/// Remaining accounts a single `refund_batch` transaction fits, every bid
+/// takes its account, receipts, token account and wallet
+const MAX_REFUND_BATCH_ACCOUNTS: usize = 24;
+
+/// Cranks refunds of every bid of the auction in batches, closing the item
+/// receipts of each bid with it. Bidders without an associated token account
+/// for the auction's mint, or with more receipts than fit in a transaction,
+/// are skipped
+pub fn refund_all(ctx: &Context, auction_address: &Pubkey, batch_size: usize, bounty: u64) -> Result<()> {
+    if batch_size == 0 {
+        bail!("batch size must be greater than zero");
+    }
+    if bounty > MAX_REFUND_BOUNTY_LAMPORTS {
+        bail!("bounty {bounty} is above the maximum of {MAX_REFUND_BOUNTY_LAMPORTS}");
+    }
+    let auction = accounts::fetch_auction(&ctx.rpc, auction_address)?;
+    let mut receipts: HashMap<Pubkey, Vec<Pubkey>> = HashMap::new();
+    for (address, receipt) in accounts::fetch_item_receipts(&ctx.rpc, auction_address, None)? {
+        receipts.entry(receipt.bidder).or_default().push(address);
+    }
+
+    let mut skipped = Vec::new();
+    let mut groups = Vec::new();
+    for (_, bid) in accounts::fetch_auction_bids(&ctx.rpc, auction_address)? {
+        let bid_receipts = receipts.remove(&bid.bidder).unwrap_or_default();
+        if bid_receipts.len() as u64 != bid.open_receipts || bid_receipts.len() + 3 > MAX_REFUND_BATCH_ACCOUNTS {
+            skipped.push(bid.bidder.to_string());
+            continue;
+        }
+        groups.push((bid.bidder, bid_receipts));
+    }
+
+    let mut refunded = Vec::new();
+    let mut signatures = Vec::new();
+    for chunk in groups.chunks(batch_size) {
+        let token_accounts: Vec<Pubkey> = chunk
+            .iter()
+            .map(|(bidder, _)| pda::token_account(bidder, &auction.token_mint))
+            .collect();
+        let (ready, missing): (Vec<_>, Vec<_>) = chunk
+            .iter()
+            .zip(ctx.rpc.get_multiple_accounts(&token_accounts)?)
+            .partition(|(_, account)| account.is_some());
+        skipped.extend(missing.into_iter().map(|((bidder, _), _)| bidder.to_string()));
+
+        // Split the ready bids further so each transaction stays under the
+        // account limit
+        let mut batch: Vec<(Pubkey, Vec<Pubkey>)> = Vec::new();
+        let mut batch_accounts = 0;
+        for ((bidder, bid_receipts), _) in ready {
+            let group_accounts = bid_receipts.len() + 3;
+            if batch_accounts + group_accounts > MAX_REFUND_BATCH_ACCOUNTS {
+                signatures.push(send_refund_batch(ctx, auction_address, &auction, &batch, bounty)?);
+                refunded.extend(batch.drain(..).map(|(bidder, _)| bidder.to_string()));
+                batch_accounts = 0;
+            }
+            batch.push((*bidder, bid_receipts.clone()));
+            batch_accounts += group_accounts;
+        }
+        if !batch.is_empty() {
+            signatures.push(send_refund_batch(ctx, auction_address, &auction, &batch, bounty)?);
+            refunded.extend(batch.into_iter().map(|(bidder, _)| bidder.to_string()));
+        }
+    }
+
+    ctx.print(&json!({
+        "refunded": refunded,
+        "skipped": skipped,
+        "signatures": signatures,
+    }));
+    Ok(())
+}
+
+fn send_refund_batch(
+    ctx: &Context,
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    bids: &[(Pubkey, Vec<Pubkey>)],
+    bounty: u64,
+) -> Result<String> {
+    let signature = ctx.send(
+        &[instructions::refund_batch(auction_address, auction, &ctx.payer()?.pubkey(), bids, bounty)],
+        &[],
+    )?;
+    Ok(signature.to_string())
+}
+
+fn parse_hash(hex: &str) -> Result<[u8; 32]> {
+    let hex = hex.trim_start_matches("0x");
+    if hex.len() != 64 || !hex.is_ascii() {
+        bail!("invalid proof hash {hex}");
+    }
+    let mut hash = [0u8; 32];
+    for (byte, chunk) in hash.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        let chunk = std::str::from_utf8(chunk)?;
+        *byte = u8::from_str_radix(chunk, 16)?;
+    }
+    Ok(hash)
+}
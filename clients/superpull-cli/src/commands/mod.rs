@@ -0,0 +1,11 @@
+pub mod auction;
+pub mod bid;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Current unix timestamp, used to quote prices and check deadlines
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() as i64)
+}
@@ -0,0 +1,120 @@
+use anchor_client::{
+    solana_client::rpc_client::RpcClient,
+    solana_sdk::{
+        commitment_config::CommitmentConfig,
+        instruction::Instruction,
+        signature::{read_keypair_file, Keypair, Signature, Signer},
+        transaction::Transaction,
+    },
+};
+use anyhow::{anyhow, Context as _, Result};
+use serde_json::Value;
+use std::str::FromStr;
+
+/// Connection, fee payer and output format shared by all commands
+pub struct Context {
+    pub rpc: RpcClient,
+    keypair_path: String,
+    payer: Option<Keypair>,
+    pub json: bool,
+}
+
+impl Context {
+    /// Loads the RPC URL, commitment and keypair from the Solana CLI config,
+    /// `url` and `keypair` override the configured values
+    pub fn load(
+        config_file: Option<&str>,
+        url: Option<String>,
+        keypair: Option<String>,
+        json: bool,
+    ) -> Result<Self> {
+        let config = match config_file {
+            Some(path) => solana_cli_config::Config::load(path)
+                .map_err(|err| anyhow!("failed to load config {path}: {err}"))?,
+            // Fall back to the defaults when the CLI was never configured
+            None => solana_cli_config::CONFIG_FILE
+                .as_deref()
+                .and_then(|path| solana_cli_config::Config::load(path).ok())
+                .unwrap_or_default(),
+        };
+
+        let url = url.unwrap_or(config.json_rpc_url);
+        let commitment = CommitmentConfig::from_str(&config.commitment)
+            .unwrap_or_else(|_| CommitmentConfig::confirmed());
+        let keypair_path = keypair.unwrap_or(config.keypair_path);
+        // Read-only commands work without a keypair
+        let payer = read_keypair_file(&keypair_path).ok();
+
+        Ok(Self {
+            rpc: RpcClient::new_with_commitment(url, commitment),
+            keypair_path,
+            payer,
+            json,
+        })
+    }
+
+    /// Signer paying for and authorizing transactions
+    pub fn payer(&self) -> Result<&Keypair> {
+        self.payer
+            .as_ref()
+            .ok_or_else(|| anyhow!("failed to read keypair {}", self.keypair_path))
+    }
+
+    /// Signs with the payer and `signers` and sends the instructions in a
+    /// single transaction
+    pub fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<Signature> {
+        let payer = self.payer()?;
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let mut all_signers: Vec<&Keypair> = vec![payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        self.rpc
+            .send_and_confirm_transaction(&transaction)
+            .context("transaction failed")
+    }
+
+    /// Prints `value` as JSON, or as `key: value` lines
+    pub fn print(&self, value: &Value) {
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(value).unwrap_or_default());
+            return;
+        }
+        match value {
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        println!();
+                    }
+                    print_fields(item);
+                }
+            }
+            value => print_fields(value),
+        }
+    }
+
+    /// Prints a progress message, suppressed in JSON mode to keep the output parseable
+    pub fn log(&self, message: &str) {
+        if !self.json {
+            eprintln!("{message}");
+        }
+    }
+}
+
+fn print_fields(value: &Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                match field {
+                    Value::String(text) => println!("{key}: {text}"),
+                    field => println!("{key}: {field}"),
+                }
+            }
+        }
+        value => println!("{value}"),
+    }
+}
@@ -0,0 +1,102 @@
+//! `superpull` command-line tool for creating and operating SuperPull auctions
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+mod commands;
+mod context;
+mod setup;
+
+use commands::{auction::CreateAuctionArgs, bid::BidArgs};
+use context::Context;
+
+#[derive(Parser)]
+#[command(name = "superpull", version, about = "Create and operate SuperPull auctions")]
+struct Cli {
+    /// Solana CLI config file, defaults to ~/.config/solana/cli/config.yml
+    #[arg(long, global = true)]
+    config: Option<String>,
+    /// RPC URL, overrides the config
+    #[arg(long, short = 'u', global = true)]
+    url: Option<String>,
+    /// Keypair file of the signer, overrides the config
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<String>,
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create an auction with a new merkle tree, collection and escrow
+    CreateAuction(CreateAuctionArgs),
+    /// Show the state and current price of an auction
+    Status { auction: Pubkey },
+    /// Show the price of the next bid
+    Price { auction: Pubkey },
+    /// Bid for the next item at the current price
+    Bid(BidArgs),
+    /// List the bids of an auction
+    Bids { auction: Pubkey },
+    /// List the items sold in an auction with their price and asset id
+    Items { auction: Pubkey },
+    /// Withdraw unlocked proceeds as the auction authority
+    Withdraw {
+        auction: Pubkey,
+        /// Amount in base units, everything unlocked when unset
+        #[arg(long)]
+        amount: Option<u64>,
+    },
+    /// Refund the signer's bids of a failed or halted auction
+    Refund { auction: Pubkey },
+    /// Refund the signer's items one by one, burning their cNFTs
+    RefundItem {
+        auction: Pubkey,
+        /// Bidder who paid for the items, defaults to the signer; set it to
+        /// refund items received as a gift
+        #[arg(long)]
+        bidder: Option<Pubkey>,
+        /// Serial of a single item to refund, every item when unset
+        #[arg(long)]
+        serial: Option<u64>,
+    },
+    /// Refund every bidder of a failed or halted auction, closing their bids
+    RefundAll {
+        auction: Pubkey,
+        /// Bidders refunded per transaction
+        #[arg(long, default_value_t = 6)]
+        batch_size: usize,
+        /// Lamports taken from the rent of each closed bid, the rest goes
+        /// back to the bidder
+        #[arg(long, default_value_t = 0)]
+        bounty: u64,
+    },
+    /// Close an auction, halting it so backers can refund
+    Close { auction: Pubkey },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = Context::load(cli.config.as_deref(), cli.url, cli.keypair, cli.json)?;
+
+    match cli.command {
+        Command::CreateAuction(args) => commands::auction::create(&ctx, args),
+        Command::Status { auction } => commands::auction::status(&ctx, &auction),
+        Command::Price { auction } => commands::auction::price(&ctx, &auction),
+        Command::Bid(args) => commands::bid::bid(&ctx, args),
+        Command::Bids { auction } => commands::bid::list(&ctx, &auction),
+        Command::Items { auction } => commands::bid::items(&ctx, &auction),
+        Command::Withdraw { auction, amount } => commands::auction::withdraw(&ctx, &auction, amount),
+        Command::Refund { auction } => commands::bid::refund(&ctx, &auction),
+        Command::RefundItem { auction, bidder, serial } => {
+            commands::bid::refund_item(&ctx, &auction, bidder, serial)
+        }
+        Command::RefundAll { auction, batch_size, bounty } => {
+            commands::bid::refund_all(&ctx, &auction, batch_size, bounty)
+        }
+        Command::Close { auction } => commands::auction::close(&ctx, &auction),
+    }
+}
@@ -0,0 +1,128 @@
+//! Instructions creating the accounts an auction needs besides its own
+//! state: the Bubblegum tree, the collection NFT and the payment escrow
+use anchor_client::solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_instruction};
+use anchor_spl::{associated_token::spl_associated_token_account, token};
+use mpl_bubblegum::{
+    instructions::{CreateTreeConfigBuilder, SetTreeDelegateBuilder},
+    programs::SPL_ACCOUNT_COMPRESSION_ID,
+};
+use mpl_token_metadata::{
+    instructions::{CreateV1Builder, MintV1Builder, UpdateV1Builder},
+    types::{CollectionDetails, TokenStandard},
+};
+use superpull_client::pda;
+
+/// Size of a concurrent merkle tree account without canopy
+pub fn merkle_tree_account_size(max_depth: u32, max_buffer_size: u32) -> usize {
+    let (depth, buffer_size) = (max_depth as usize, max_buffer_size as usize);
+    let header = 2 + 54;
+    // root, path, index and padding
+    let change_log = 32 + 32 * depth + 4 + 4;
+    // proof, leaf, index and padding
+    let rightmost_path = 32 * depth + 32 + 4 + 4;
+    // sequence number, active index and buffer size
+    header + 8 + 8 + 8 + buffer_size * change_log + rightmost_path
+}
+
+/// Allocates `merkle_tree` and creates its private Bubblegum tree config
+pub fn create_tree(
+    payer: &Pubkey,
+    merkle_tree: &Pubkey,
+    max_depth: u32,
+    max_buffer_size: u32,
+    rent_lamports: u64,
+) -> Vec<Instruction> {
+    vec![
+        system_instruction::create_account(
+            payer,
+            merkle_tree,
+            rent_lamports,
+            merkle_tree_account_size(max_depth, max_buffer_size) as u64,
+            &SPL_ACCOUNT_COMPRESSION_ID,
+        ),
+        CreateTreeConfigBuilder::new()
+            .tree_config(pda::find_tree_config(merkle_tree).0)
+            .merkle_tree(*merkle_tree)
+            .payer(*payer)
+            .tree_creator(*payer)
+            .max_depth(max_depth)
+            .max_buffer_size(max_buffer_size)
+            .public(false)
+            .instruction(),
+    ]
+}
+
+/// Creates the collection NFT and mints it to `payer`
+pub fn create_collection(
+    payer: &Pubkey,
+    collection_mint: &Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Vec<Instruction> {
+    let metadata = pda::find_metadata(collection_mint).0;
+    let master_edition = pda::find_edition(collection_mint).0;
+    vec![
+        CreateV1Builder::new()
+            .metadata(metadata)
+            .master_edition(Some(master_edition))
+            .mint(*collection_mint, true)
+            .authority(*payer)
+            .payer(*payer)
+            .update_authority(*payer, true)
+            .spl_token_program(Some(token::ID))
+            .name(name)
+            .symbol(symbol)
+            .uri(uri)
+            .seller_fee_basis_points(0)
+            .token_standard(TokenStandard::NonFungible)
+            .collection_details(CollectionDetails::V1 { size: 0 })
+            .instruction(),
+        MintV1Builder::new()
+            .token(pda::token_account(payer, collection_mint))
+            .token_owner(Some(*payer))
+            .metadata(metadata)
+            .master_edition(Some(master_edition))
+            .mint(*collection_mint)
+            .authority(*payer)
+            .payer(*payer)
+            .amount(1)
+            .instruction(),
+    ]
+}
+
+/// Hands the collection and the tree over to the auction PDA, which verifies
+/// every minted item
+pub fn delegate_to_auction(
+    payer: &Pubkey,
+    auction: &Pubkey,
+    collection_mint: &Pubkey,
+    merkle_tree: &Pubkey,
+) -> Vec<Instruction> {
+    vec![
+        UpdateV1Builder::new()
+            .authority(*payer)
+            .mint(*collection_mint)
+            .metadata(pda::find_metadata(collection_mint).0)
+            .edition(Some(pda::find_edition(collection_mint).0))
+            .payer(*payer)
+            .new_update_authority(*auction)
+            .instruction(),
+        SetTreeDelegateBuilder::new()
+            .tree_config(pda::find_tree_config(merkle_tree).0)
+            .tree_creator(*payer)
+            .new_tree_delegate(*auction)
+            .merkle_tree(*merkle_tree)
+            .instruction(),
+    ]
+}
+
+/// Creates the auction's escrow token account for the payment mint
+pub fn create_escrow(payer: &Pubkey, auction: &Pubkey, token_mint: &Pubkey) -> Instruction {
+    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+        payer,
+        auction,
+        token_mint,
+        &token::ID,
+    )
+}
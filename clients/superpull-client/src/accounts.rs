@@ -0,0 +1,139 @@
+use anchor_client::solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use solana_account_decoder::UiAccountEncoding;
+use superpull_program::state::{
+    AuctionMilestones, AuctionPhases, AuctionState, BidState, GlobalConfig, ItemReceipt,
+    Registry, RegistryPage, SellerCounter,
+};
+
+use crate::{pda, ClientError};
+
+/// Decodes a program account, checking its discriminator
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    Ok(T::try_deserialize(&mut &data[..])?)
+}
+
+/// Fetches and decodes a program account
+pub fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> Result<T, ClientError> {
+    let data = rpc.get_account_data(address)?;
+    decode(&data)
+}
+
+/// Fetches a program account, `None` when it does not exist
+pub fn fetch_optional<T: AccountDeserialize>(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<T>, ClientError> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())?
+        .value;
+    account.map(|account| decode(&account.data)).transpose()
+}
+
+pub fn fetch_auction(rpc: &RpcClient, address: &Pubkey) -> Result<AuctionState, ClientError> {
+    fetch(rpc, address)
+}
+
+pub fn fetch_bid(rpc: &RpcClient, auction: &Pubkey, bidder: &Pubkey) -> Result<Option<BidState>, ClientError> {
+    fetch_optional(rpc, &pda::find_bid(auction, bidder).0)
+}
+
+/// Fetches every bid of `auction`
+pub fn fetch_auction_bids(rpc: &RpcClient, auction: &Pubkey) -> Result<Vec<(Pubkey, BidState)>, ClientError> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, BidState::DISCRIMINATOR.to_vec())),
+            // `auction` follows the discriminator
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, auction.to_bytes().to_vec())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    rpc.get_program_accounts_with_config(&superpull_program::ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode(&account.data)?)))
+        .collect()
+}
+
+/// Fetches the item receipts of `auction`, or of a single bidder in it
+pub fn fetch_item_receipts(
+    rpc: &RpcClient,
+    auction: &Pubkey,
+    bidder: Option<&Pubkey>,
+) -> Result<Vec<(Pubkey, ItemReceipt)>, ClientError> {
+    let mut filters = vec![
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, ItemReceipt::DISCRIMINATOR.to_vec())),
+        // `auction` follows the discriminator
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, auction.to_bytes().to_vec())),
+    ];
+    if let Some(bidder) = bidder {
+        // `bidder` follows `auction` and `serial`
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(48, bidder.to_bytes().to_vec())));
+    }
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut receipts = rpc
+        .get_program_accounts_with_config(&superpull_program::ID, config)?
+        .into_iter()
+        .map(|(address, account)| Ok((address, decode::<ItemReceipt>(&account.data)?)))
+        .collect::<Result<Vec<_>, ClientError>>()?;
+    receipts.sort_by_key(|(_, receipt)| receipt.serial);
+    Ok(receipts)
+}
+
+pub fn fetch_config(rpc: &RpcClient) -> Result<GlobalConfig, ClientError> {
+    fetch(rpc, &pda::find_config().0)
+}
+
+pub fn fetch_phases(rpc: &RpcClient, auction: &Pubkey) -> Result<Option<AuctionPhases>, ClientError> {
+    fetch_optional(rpc, &pda::find_phases(auction).0)
+}
+
+pub fn fetch_milestones(
+    rpc: &RpcClient,
+    auction: &Pubkey,
+) -> Result<Option<AuctionMilestones>, ClientError> {
+    fetch_optional(rpc, &pda::find_milestones(auction).0)
+}
+
+pub fn fetch_registry(rpc: &RpcClient, authority: Option<&Pubkey>) -> Result<Option<Registry>, ClientError> {
+    fetch_optional(rpc, &pda::find_registry(authority).0)
+}
+
+pub fn fetch_registry_page(
+    rpc: &RpcClient,
+    registry: &Pubkey,
+    page_index: u32,
+) -> Result<Option<RegistryPage>, ClientError> {
+    fetch_optional(rpc, &pda::find_registry_page(registry, page_index).0)
+}
+
+/// Id the authority's next auction will get
+pub fn fetch_next_auction_id(rpc: &RpcClient, authority: &Pubkey) -> Result<u64, ClientError> {
+    let counter: Option<SellerCounter> = fetch_optional(rpc, &pda::find_seller_counter(authority).0)?;
+    Ok(counter.map_or(1, |counter| counter.next_auction_id()))
+}
+
+/// Pages of the global and the authority's registry the next auction of
+/// `authority` is appended to
+pub fn fetch_current_registry_pages(rpc: &RpcClient, authority: &Pubkey) -> Result<(u32, u32), ClientError> {
+    let global = fetch_registry(rpc, None)?;
+    let authority = fetch_registry(rpc, Some(authority))?;
+    Ok((
+        global.map_or(0, |registry| registry.current_page()),
+        authority.map_or(0, |registry| registry.current_page()),
+    ))
+}
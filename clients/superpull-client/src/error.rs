@@ -0,0 +1,16 @@
+use anchor_client::solana_client::client_error::ClientError as RpcError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<RpcError>),
+    #[error("Failed to decode account: {0}")]
+    Decode(#[from] anchor_lang::error::Error),
+}
+
+impl From<RpcError> for ClientError {
+    fn from(error: RpcError) -> Self {
+        Self::Rpc(Box::new(error))
+    }
+}
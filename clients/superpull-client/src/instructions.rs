@@ -0,0 +1,765 @@
+//! Instruction builders for every `superpull_program` instruction. Program
+//! accounts, PDAs and token accounts are derived, Bubblegum, compression and
+//! noop accounts are filled in
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{instruction::Instruction, system_program},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{metadata, token};
+use mpl_bubblegum::programs::{SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID};
+use superpull_program::{
+    accounts,
+    instruction,
+    instructions::{AllowlistProof, ItemLeaf},
+    state::{ArbiterRuling, AuctionState, GateRule, PhaseArgs, ProposalAction, UpdateAuctionArgs},
+    ID,
+};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize_config(
+    admin: &Pubkey,
+    treasury: &Pubkey,
+    protocol_fee_bps: u16,
+    allowed_mints: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            config: pda::find_config().0,
+            admin: *admin,
+            treasury: *treasury,
+            program: ID,
+            program_data: pda::find_program_data().0,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+        },
+        instruction::InitializeConfig {
+            protocol_fee_bps,
+            allowed_mints,
+        },
+    )
+}
+
+pub fn update_config(admin: &Pubkey, args: instruction::UpdateConfig) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            config: pda::find_config().0,
+            admin: *admin,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        args,
+    )
+}
+
+/// Creates auction `auction_id` of `authority`, appended to the given pages
+/// of the global and the authority's registry. See
+/// [`crate::accounts::fetch_next_auction_id`] and
+/// [`crate::accounts::fetch_current_registry_pages`]
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_auction(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    merkle_tree: &Pubkey,
+    collection_mint: &Pubkey,
+    token_mint: &Pubkey,
+    auction_id: u64,
+    registry_pages: (u32, u32),
+    args: instruction::InitializeAuction,
+) -> Instruction {
+    let global_registry = pda::find_registry(None).0;
+    let authority_registry = pda::find_registry(Some(authority)).0;
+    build(
+        accounts::InitializeAuction {
+            authority: *authority,
+            seller_counter: pda::find_seller_counter(authority).0,
+            auction: pda::find_auction(authority, collection_mint, auction_id).0,
+            global_registry,
+            global_registry_page: pda::find_registry_page(&global_registry, registry_pages.0).0,
+            authority_registry,
+            authority_registry_page: pda::find_registry_page(&authority_registry, registry_pages.1).0,
+            config: pda::find_config().0,
+            merkle_tree: *merkle_tree,
+            collection_mint: *collection_mint,
+            token_mint: *token_mint,
+            payer: *payer,
+            bubblegum_program: mpl_bubblegum::ID,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        args,
+    )
+}
+
+pub fn propose_authority(auction: &Pubkey, authority: &Pubkey, new_authority: Option<Pubkey>) -> Instruction {
+    build(
+        accounts::ProposeAuthority {
+            auction: *auction,
+            authority: *authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::ProposeAuthority { new_authority },
+    )
+}
+
+pub fn accept_authority(auction: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAuthority {
+            auction: *auction,
+            new_authority: *new_authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::AcceptAuthority {},
+    )
+}
+
+pub fn set_treasury(auction: &Pubkey, authority: &Pubkey, treasury: Pubkey) -> Instruction {
+    build(
+        accounts::SetTreasury {
+            auction: *auction,
+            authority: *authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::SetTreasury { treasury },
+    )
+}
+
+pub fn set_sell_back(auction: &Pubkey, authority: &Pubkey, spread_bps: Option<u16>) -> Instruction {
+    build(
+        accounts::SetSellBack {
+            auction: *auction,
+            authority: *authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::SetSellBack { spread_bps },
+    )
+}
+
+pub fn update_auction(auction: &Pubkey, authority: &Pubkey, args: UpdateAuctionArgs) -> Instruction {
+    build(
+        accounts::UpdateAuction {
+            auction: *auction,
+            authority: *authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::UpdateAuction { args },
+    )
+}
+
+pub fn cancel_auction(auction: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelAuction {
+            auction: *auction,
+            authority: *authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::CancelAuction {},
+    )
+}
+
+/// Creates the auction's next proposal, `proposal_index` is the auction's
+/// current `proposal_count`
+pub fn create_proposal(
+    auction: &Pubkey,
+    proposal_index: u32,
+    proposer: &Pubkey,
+    payer: &Pubkey,
+    action: ProposalAction,
+) -> Instruction {
+    build(
+        accounts::CreateProposal {
+            auction: *auction,
+            proposal: pda::find_proposal(auction, proposal_index).0,
+            proposer: *proposer,
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::CreateProposal { action },
+    )
+}
+
+pub fn approve_proposal(auction: &Pubkey, proposal_index: u32, signer: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveProposal {
+            auction: *auction,
+            proposal: pda::find_proposal(auction, proposal_index).0,
+            signer: *signer,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::ApproveProposal {},
+    )
+}
+
+/// Executes an approved `Cancel`, `Update`, `ProposeAuthority` or
+/// `SetSellBack` proposal
+pub fn execute_proposal(auction: &Pubkey, proposal_index: u32) -> Instruction {
+    build(
+        accounts::ExecuteProposal {
+            auction: *auction,
+            proposal: pda::find_proposal(auction, proposal_index).0,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::ExecuteProposal {},
+    )
+}
+
+/// Executes an approved `Withdraw` proposal, with the same accounts as
+/// [`withdraw`] without the authority's signature
+pub fn execute_withdraw_proposal(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    proposal_index: u32,
+    protocol_treasury: &Pubkey,
+    payer: &Pubkey,
+) -> Instruction {
+    let mut ix = build(
+        accounts::ExecuteWithdrawProposal {
+            proposal: pda::find_proposal(auction_address, proposal_index).0,
+            funds: withdraw_funds(auction_address, auction, protocol_treasury, payer),
+        },
+        instruction::ExecuteWithdrawProposal {},
+    );
+    ix.accounts.extend(payee_accounts(auction));
+    ix
+}
+
+/// Executes an approved `SetPhases` proposal, `phases` must match the
+/// approved [`superpull_program::state::phases_hash`]
+pub fn execute_phases_proposal(
+    auction: &Pubkey,
+    proposal_index: u32,
+    payer: &Pubkey,
+    phases: Vec<PhaseArgs>,
+) -> Instruction {
+    build(
+        accounts::ExecutePhasesProposal {
+            auction: *auction,
+            proposal: pda::find_proposal(auction, proposal_index).0,
+            phases: pda::find_phases(auction).0,
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::ExecutePhasesProposal { phases },
+    )
+}
+
+/// Executes an approved `SetMilestones` proposal, the milestones must match
+/// the approved [`superpull_program::state::milestones_hash`]
+pub fn execute_milestones_proposal(
+    auction: &Pubkey,
+    proposal_index: u32,
+    payer: &Pubkey,
+    release_bps: Vec<u16>,
+    challenge_period: i64,
+    veto_threshold_bps: u16,
+) -> Instruction {
+    build(
+        accounts::ExecuteMilestonesProposal {
+            auction: *auction,
+            proposal: pda::find_proposal(auction, proposal_index).0,
+            milestones: pda::find_milestones(auction).0,
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::ExecuteMilestonesProposal {
+            release_bps,
+            challenge_period,
+            veto_threshold_bps,
+        },
+    )
+}
+
+pub fn set_phases(auction: &Pubkey, authority: &Pubkey, payer: &Pubkey, phases: Vec<PhaseArgs>) -> Instruction {
+    build(
+        accounts::SetPhases {
+            auction: *auction,
+            phases: pda::find_phases(auction).0,
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::SetPhases { phases },
+    )
+}
+
+pub fn set_milestones(
+    auction: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    release_bps: Vec<u16>,
+    challenge_period: i64,
+    veto_threshold_bps: u16,
+) -> Instruction {
+    build(
+        accounts::SetMilestones {
+            auction: *auction,
+            milestones: pda::find_milestones(auction).0,
+            authority: *authority,
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::SetMilestones {
+            release_bps,
+            challenge_period,
+            veto_threshold_bps,
+        },
+    )
+}
+
+pub fn submit_milestone(auction: &Pubkey, authority: &Pubkey, evidence_hash: [u8; 32]) -> Instruction {
+    build(
+        accounts::SubmitMilestone {
+            auction: *auction,
+            milestones: pda::find_milestones(auction).0,
+            authority: *authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::SubmitMilestone { evidence_hash },
+    )
+}
+
+pub fn veto_milestone(auction: &Pubkey, bidder: &Pubkey, milestone_index: u8) -> Instruction {
+    build(
+        accounts::VetoMilestone {
+            auction: *auction,
+            milestones: pda::find_milestones(auction).0,
+            bid: pda::find_bid(auction, bidder).0,
+            bidder: *bidder,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::VetoMilestone { milestone_index },
+    )
+}
+
+pub fn get_current_price(auction_address: &Pubkey, auction: &AuctionState) -> Instruction {
+    build(
+        accounts::GetCurrentPrice {
+            auction: *auction_address,
+            phases: auction.has_phases.then(|| pda::find_phases(auction_address).0),
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::GetCurrentPrice {},
+    )
+}
+
+/// Bids `amount` for the next item, minted to `recipient` when set and to
+/// `bidder` otherwise. `gate_nft_mint` is the NFT proving a collection gate,
+/// the bidder's token account of the gate mint is used for token balance
+/// gates
+#[allow(clippy::too_many_arguments)]
+pub fn place_bid(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    bidder: &Pubkey,
+    payer: &Pubkey,
+    recipient: Option<&Pubkey>,
+    amount: u64,
+    allowlist_proof: Option<AllowlistProof>,
+    referrer: Option<&Pubkey>,
+    gate_nft_mint: Option<&Pubkey>,
+) -> Instruction {
+    let mut ix = build(
+        accounts::PlaceBid {
+            auction: *auction_address,
+            bid: pda::find_bid(auction_address, bidder).0,
+            receipt: pda::find_item_receipt(auction_address, auction.next_item_serial()).0,
+            phases: auction.has_phases.then(|| pda::find_phases(auction_address).0),
+            referrer_state: referrer.map(|referrer| pda::find_referrer(auction_address, referrer).0),
+            bidder: *bidder,
+            payer: *payer,
+            recipient: recipient.copied(),
+            bidder_token_account: pda::token_account(bidder, &auction.token_mint),
+            auction_token_account: pda::token_account(auction_address, &auction.token_mint),
+            collection_mint: auction.collection_mint,
+            collection_metadata: pda::find_metadata(&auction.collection_mint).0,
+            collection_edition: pda::find_edition(&auction.collection_mint).0,
+            merkle_tree: auction.merkle_tree,
+            tree_config: pda::find_tree_config(&auction.merkle_tree).0,
+            tree_creator: *auction_address,
+            bubblegum_signer: pda::find_bubblegum_signer().0,
+            token_metadata_program: metadata::ID,
+            compression_program: SPL_ACCOUNT_COMPRESSION_ID,
+            log_wrapper: SPL_NOOP_ID,
+            bubblegum_program: mpl_bubblegum::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::PlaceBid {
+            amount,
+            allowlist_proof,
+        },
+    );
+    ix.accounts.extend(gate_accounts(auction_address, auction, bidder, gate_nft_mint));
+    ix
+}
+
+/// Remaining accounts proving the auction's token gate
+fn gate_accounts(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    bidder: &Pubkey,
+    gate_nft_mint: Option<&Pubkey>,
+) -> Vec<AccountMeta> {
+    match (auction.gate, gate_nft_mint) {
+        (Some(GateRule::TokenBalance { mint, .. }), _) => {
+            vec![AccountMeta::new_readonly(pda::token_account(bidder, &mint), false)]
+        }
+        (Some(GateRule::Collection { one_use_per_nft, .. }), Some(nft_mint)) => {
+            let mut accounts = vec![
+                AccountMeta::new_readonly(pda::token_account(bidder, nft_mint), false),
+                AccountMeta::new_readonly(pda::find_metadata(nft_mint).0, false),
+            ];
+            if one_use_per_nft {
+                accounts.push(AccountMeta::new(pda::find_gate_marker(auction_address, nft_mint).0, false));
+            }
+            accounts
+        }
+        _ => Vec::new(),
+    }
+}
+
+pub fn register_referrer(auction: &Pubkey, referrer: &Pubkey) -> Instruction {
+    build(
+        accounts::RegisterReferrer {
+            auction: *auction,
+            referrer_state: pda::find_referrer(auction, referrer).0,
+            referrer: *referrer,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::RegisterReferrer {},
+    )
+}
+
+pub fn claim_referral_rewards(auction_address: &Pubkey, auction: &AuctionState, referrer: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimReferralRewards {
+            auction: *auction_address,
+            referrer_state: pda::find_referrer(auction_address, referrer).0,
+            referrer: *referrer,
+            referrer_token_account: pda::token_account(referrer, &auction.token_mint),
+            auction_token_account: pda::token_account(auction_address, &auction.token_mint),
+            token_program: token::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::ClaimReferralRewards {},
+    )
+}
+
+pub fn confirm_delivery(auction: &Pubkey, authority: &Pubkey) -> Instruction {
+    build(
+        accounts::ConfirmDelivery {
+            auction: *auction,
+            authority: *authority,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::ConfirmDelivery {},
+    )
+}
+
+/// Records a ruling, `ruling_index` is the auction's current `ruling_count`
+pub fn arbitrate(
+    auction: &Pubkey,
+    ruling_index: u32,
+    arbiter: &Pubkey,
+    payer: &Pubkey,
+    ruling: ArbiterRuling,
+    reason_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::Arbitrate {
+            auction: *auction,
+            ruling_record: pda::find_ruling(auction, ruling_index).0,
+            arbiter: *arbiter,
+            payer: *payer,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::Arbitrate {
+            ruling,
+            reason_hash,
+        },
+    )
+}
+
+pub fn get_withdrawable(auction_address: &Pubkey, auction: &AuctionState) -> Instruction {
+    build(
+        accounts::GetWithdrawable {
+            auction: *auction_address,
+            milestones: auction.has_milestones.then(|| pda::find_milestones(auction_address).0),
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::GetWithdrawable {},
+    )
+}
+
+fn withdraw_funds(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    protocol_treasury: &Pubkey,
+    payer: &Pubkey,
+) -> accounts::WithdrawFunds {
+    accounts::WithdrawFunds {
+        auction: *auction_address,
+        config: pda::find_config().0,
+        milestones: auction.has_milestones.then(|| pda::find_milestones(auction_address).0),
+        authority_token_account: auction
+            .payees
+            .is_empty()
+            .then(|| pda::token_account(&auction.payout_wallet(), &auction.token_mint)),
+        treasury_token_account: pda::token_account(protocol_treasury, &auction.token_mint),
+        auction_token_account: pda::token_account(auction_address, &auction.token_mint),
+        payer: *payer,
+        system_program: system_program::ID,
+        token_program: token::ID,
+        event_authority: pda::find_event_authority().0,
+        program: ID,
+    }
+}
+
+/// Token accounts of the payees, in payee order
+fn payee_accounts(auction: &AuctionState) -> Vec<AccountMeta> {
+    auction
+        .payees
+        .iter()
+        .map(|payee| AccountMeta::new(pda::token_account(&payee.wallet, &auction.token_mint), false))
+        .collect()
+}
+
+/// Withdraws `amount` of the unlocked proceeds, or all of them when unset.
+/// `protocol_treasury` is the config's treasury wallet, signed by the
+/// auction authority
+pub fn withdraw(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    protocol_treasury: &Pubkey,
+    payer: &Pubkey,
+    amount: Option<u64>,
+) -> Instruction {
+    let mut ix = build(
+        accounts::Withdraw {
+            funds: withdraw_funds(auction_address, auction, protocol_treasury, payer),
+            authority: auction.authority,
+        },
+        instruction::Withdraw { amount },
+    );
+    ix.accounts.extend(payee_accounts(auction));
+    ix
+}
+
+/// Refunds `bidder`'s whole position and closes its open item `receipts`,
+/// see [`crate::accounts::fetch_item_receipts`]. [`refund_item`] refunds a
+/// single item and burns its cNFT
+pub fn refund(auction_address: &Pubkey, auction: &AuctionState, bidder: &Pubkey, receipts: &[Pubkey]) -> Instruction {
+    let mut ix = build(
+        accounts::Refund {
+            auction: *auction_address,
+            bid: pda::find_bid(auction_address, bidder).0,
+            bidder: *bidder,
+            bidder_token_account: pda::token_account(bidder, &auction.token_mint),
+            auction_token_account: pda::token_account(auction_address, &auction.token_mint),
+            token_program: token::ID,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::Refund {},
+    );
+    ix.accounts.extend(receipts.iter().map(|receipt| AccountMeta::new(*receipt, false)));
+    ix
+}
+
+/// Refunds the item with the given serial to `bidder` and burns its cNFT,
+/// signed by its `owner`. `referrer` is the referrer recorded in the item's
+/// receipt. `proof` is the merkle proof of the item's leaf, from the leaf
+/// up, as returned by the DAS API with the `leaf` hashes
+#[allow(clippy::too_many_arguments)]
+pub fn refund_item(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    bidder: &Pubkey,
+    owner: &Pubkey,
+    serial: u64,
+    referrer: Option<&Pubkey>,
+    leaf: ItemLeaf,
+    proof: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::RefundItem {
+            auction: *auction_address,
+            bid: pda::find_bid(auction_address, bidder).0,
+            receipt: pda::find_item_receipt(auction_address, serial).0,
+            referrer_state: referrer.map(|referrer| pda::find_referrer(auction_address, referrer).0),
+            bidder: *bidder,
+            owner: *owner,
+            bidder_token_account: pda::token_account(bidder, &auction.token_mint),
+            auction_token_account: pda::token_account(auction_address, &auction.token_mint),
+            merkle_tree: auction.merkle_tree,
+            tree_config: pda::find_tree_config(&auction.merkle_tree).0,
+            log_wrapper: SPL_NOOP_ID,
+            compression_program: SPL_ACCOUNT_COMPRESSION_ID,
+            bubblegum_program: mpl_bubblegum::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::RefundItem { leaf },
+    );
+    ix.accounts
+        .extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    ix
+}
+
+/// Refunds the bidders of `bids` to their associated token accounts and
+/// closes their bid accounts, signed by any `cranker` who takes up to
+/// `bounty` lamports of each bid's rent. Each bidder comes with the
+/// addresses of its bid's open item receipts, which are closed with it
+pub fn refund_batch(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    cranker: &Pubkey,
+    bids: &[(Pubkey, Vec<Pubkey>)],
+    bounty: u64,
+) -> Instruction {
+    let mut ix = build(
+        accounts::RefundBatch {
+            auction: *auction_address,
+            cranker: *cranker,
+            auction_token_account: pda::token_account(auction_address, &auction.token_mint),
+            token_program: token::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::RefundBatch { bounty },
+    );
+    for (bidder, receipts) in bids {
+        ix.accounts.push(AccountMeta::new(pda::find_bid(auction_address, bidder).0, false));
+        ix.accounts.extend(receipts.iter().map(|receipt| AccountMeta::new(*receipt, false)));
+        ix.accounts.push(AccountMeta::new(pda::token_account(bidder, &auction.token_mint), false));
+        ix.accounts.push(AccountMeta::new(*bidder, false));
+    }
+    ix
+}
+
+/// Sells the item with the given serial back to the curve and burns its
+/// cNFT. `referrer` and `proof` are the receipt's referrer and the merkle
+/// proof of the item's leaf, as for `refund_item`
+pub fn sell_back(
+    auction_address: &Pubkey,
+    auction: &AuctionState,
+    bidder: &Pubkey,
+    serial: u64,
+    referrer: Option<&Pubkey>,
+    leaf: ItemLeaf,
+    proof: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SellBack {
+            auction: *auction_address,
+            bid: pda::find_bid(auction_address, bidder).0,
+            receipt: pda::find_item_receipt(auction_address, serial).0,
+            referrer_state: referrer.map(|referrer| pda::find_referrer(auction_address, referrer).0),
+            bidder: *bidder,
+            bidder_token_account: pda::token_account(bidder, &auction.token_mint),
+            auction_token_account: pda::token_account(auction_address, &auction.token_mint),
+            merkle_tree: auction.merkle_tree,
+            tree_config: pda::find_tree_config(&auction.merkle_tree).0,
+            log_wrapper: SPL_NOOP_ID,
+            compression_program: SPL_ACCOUNT_COMPRESSION_ID,
+            bubblegum_program: mpl_bubblegum::ID,
+            token_program: token::ID,
+            system_program: system_program::ID,
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::SellBack { leaf },
+    );
+    ix.accounts
+        .extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    ix
+}
+
+/// Refreshes the registry entries of an auction, `None` for auctions that
+/// are not listed
+pub fn sync_registry(auction_address: &Pubkey, auction: &AuctionState) -> Option<Instruction> {
+    let position = auction.registry_position?;
+    let global_registry = pda::find_registry(None).0;
+    let authority_registry = pda::find_registry(Some(auction.seed_authority())).0;
+    let page = |registry: &Pubkey, index: u64| {
+        let (page_index, _) = superpull_program::state::registry_location(index);
+        pda::find_registry_page(registry, page_index).0
+    };
+    Some(build(
+        accounts::SyncRegistry {
+            auction: *auction_address,
+            global_registry,
+            global_registry_page: page(&global_registry, position.global_index),
+            authority_registry,
+            authority_registry_page: page(&authority_registry, position.authority_index),
+            event_authority: pda::find_event_authority().0,
+            program: ID,
+        },
+        instruction::SyncRegistry {},
+    ))
+}
+
+pub fn migrate_auction(auction: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateAuction {
+            auction: *auction,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateAuction {},
+    )
+}
+
+pub fn migrate_bid(bid: &Pubkey, payer: &Pubkey) -> Instruction {
+    build(
+        accounts::MigrateBid {
+            bid: *bid,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateBid {},
+    )
+}
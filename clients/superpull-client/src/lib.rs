@@ -0,0 +1,11 @@
+//! Rust client for the SuperPull program: PDA derivation, instruction
+//! builders, account fetching and price quotes
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use error::ClientError;
+pub use superpull_program::{self, state, ID};
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{associated_token::get_associated_token_address, metadata};
+use superpull_program::{utils::events::EVENT_AUTHORITY_SEED, ID};
+
+/// Global protocol configuration
+pub fn find_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &ID)
+}
+
+/// Counter handing out the authority's auction ids
+pub fn find_seller_counter(authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"seller", authority.as_ref()], &ID)
+}
+
+/// Auction with the given id, id 0 derives the address of auctions created
+/// before ids existed
+pub fn find_auction(authority: &Pubkey, collection_mint: &Pubkey, auction_id: u64) -> (Pubkey, u8) {
+    let auction_id_seed = if auction_id == 0 {
+        Vec::new()
+    } else {
+        auction_id.to_le_bytes().to_vec()
+    };
+    Pubkey::find_program_address(
+        &[b"auction", authority.as_ref(), collection_mint.as_ref(), &auction_id_seed],
+        &ID,
+    )
+}
+
+/// Bid account of `bidder` in `auction`
+pub fn find_bid(auction: &Pubkey, bidder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"bid", auction.as_ref(), bidder.as_ref()], &ID)
+}
+
+/// Receipt of the item with the given serial number in `auction`
+pub fn find_item_receipt(auction: &Pubkey, serial: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"receipt", auction.as_ref(), &serial.to_le_bytes()], &ID)
+}
+
+pub fn find_phases(auction: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"phases", auction.as_ref()], &ID)
+}
+
+pub fn find_milestones(auction: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"milestones", auction.as_ref()], &ID)
+}
+
+pub fn find_referrer(auction: &Pubkey, referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"referrer", auction.as_ref(), referrer.as_ref()], &ID)
+}
+
+/// Marker of an NFT used for a one-use collection gate
+pub fn find_gate_marker(auction: &Pubkey, nft_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"gate_marker", auction.as_ref(), nft_mint.as_ref()], &ID)
+}
+
+pub fn find_ruling(auction: &Pubkey, index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"ruling", auction.as_ref(), &index.to_le_bytes()], &ID)
+}
+
+pub fn find_proposal(auction: &Pubkey, index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"proposal", auction.as_ref(), &index.to_le_bytes()], &ID)
+}
+
+/// Global registry, or the registry of `authority`
+pub fn find_registry(authority: Option<&Pubkey>) -> (Pubkey, u8) {
+    match authority {
+        Some(authority) => Pubkey::find_program_address(&[b"registry", authority.as_ref()], &ID),
+        None => Pubkey::find_program_address(&[b"registry"], &ID),
+    }
+}
+
+pub fn find_registry_page(registry: &Pubkey, page_index: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"registry_page", registry.as_ref(), &page_index.to_le_bytes()],
+        &ID,
+    )
+}
+
+/// Bubblegum PDA that signs collection verification
+pub fn find_bubblegum_signer() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"collection_cpi"], &mpl_bubblegum::ID)
+}
+
+/// Bubblegum configuration of a merkle tree
+pub fn find_tree_config(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    mpl_bubblegum::accounts::TreeConfig::find_pda(merkle_tree)
+}
+
+pub fn find_metadata(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", metadata::ID.as_ref(), mint.as_ref()],
+        &metadata::ID,
+    )
+}
+
+pub fn find_edition(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"metadata", metadata::ID.as_ref(), mint.as_ref(), b"edition"],
+        &metadata::ID,
+    )
+}
+
+/// Signer of the program's event self-CPIs
+pub fn find_event_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &ID)
+}
+
+/// Program data account of the upgradeable program, holds the upgrade authority
+pub fn find_program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ID.as_ref()],
+        &anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+    )
+}
+
+/// Associated token account of `wallet` for `mint`
+pub fn token_account(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(wallet, mint)
+}
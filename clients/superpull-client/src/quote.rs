@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use superpull_program::{
+    state::{fee_amount, AuctionPhases, AuctionState},
+    utils::errors::SuperpullProgramError,
+};
+
+/// Price of the next bid as `place_bid` computes it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BidQuote {
+    /// Exact amount the bid must pay
+    pub price: u64,
+    /// Share of the price paid to the referrer, if the bid is referred
+    pub referral_fee: u64,
+    /// Phase the bid falls into, when the auction has phases
+    pub phase_index: Option<usize>,
+    /// Whether only allowlisted bidders can bid at `now`
+    pub allowlist_only: bool,
+}
+
+/// Quotes the next bid at `now`, failing with the error `place_bid` would
+/// return for an auction that does not accept bids. Per-wallet limits and
+/// allowlist proofs depend on the bidder and are not checked
+pub fn quote_bid(
+    auction: &AuctionState,
+    phases: Option<&AuctionPhases>,
+    now: i64,
+    referred: bool,
+) -> Result<BidQuote> {
+    require!(
+        now <= auction.deadline || auction.current_supply >= auction.minimum_items,
+        SuperpullProgramError::AuctionExpired
+    );
+    require!(!auction.is_halted, SuperpullProgramError::AuctionHalted);
+    require!(
+        !auction.is_delivery_failed(now),
+        SuperpullProgramError::DeliveryDeadlinePassed
+    );
+    require!(
+        auction.current_supply < auction.max_supply,
+        SuperpullProgramError::MaxSupplyReached
+    );
+
+    let mut price = auction.current_price()?;
+    let mut allowlist_only = false;
+
+    let allowlist = auction.active_allowlist(now);
+    if let Some(allowlist) = allowlist {
+        allowlist_only = true;
+        if let Some(allowlist_price) = allowlist.price {
+            price = allowlist_price;
+        }
+    }
+
+    let phase_index = if auction.has_phases {
+        let phases = phases.ok_or(SuperpullProgramError::PhasesAccountRequired)?;
+        let index = phases
+            .active_phase_index(now)
+            .ok_or(SuperpullProgramError::NoActivePhase)?;
+        let phase = &phases.phases[index];
+        require!(
+            phase.minted < phase.supply,
+            SuperpullProgramError::PhaseSupplyReached
+        );
+        allowlist_only |= phase.merkle_root.is_some();
+        price = phase.current_price()?;
+        Some(index)
+    } else {
+        None
+    };
+
+    let referral_fee = if referred {
+        fee_amount(price, auction.referral_bps).ok_or(SuperpullProgramError::MathOverflow)?
+    } else {
+        0
+    };
+
+    Ok(BidQuote {
+        price,
+        referral_fee,
+        phase_index,
+        allowlist_only,
+    })
+}
+
+/// Total cost of the next `count` items on the public bonding curve
+pub fn quote_items(auction: &AuctionState, count: u64) -> Result<u64> {
+    require!(
+        auction.current_supply
+            .checked_add(count)
+            .is_some_and(|supply| supply <= auction.max_supply),
+        SuperpullProgramError::MaxSupplyReached
+    );
+
+    let mut next = auction.clone();
+    let mut total: u64 = 0;
+    for _ in 0..count {
+        total = total
+            .checked_add(next.current_price()?)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        next.current_supply += 1;
+    }
+    Ok(total)
+}
@@ -0,0 +1,42 @@
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey};
+use superpull_program::utils::events::EVENT_AUTHORITY_SEED;
+
+use crate::events::SuperpullEvent;
+
+/// An inner instruction of a transaction, with its program and accounts
+/// resolved from the transaction's account keys
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InnerInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<Pubkey>,
+    pub data: Vec<u8>,
+}
+
+/// Decodes the events the program emitted through its event self-CPI.
+///
+/// Only instructions to the program signed by its event authority are
+/// decoded, the program rejects any other event instruction so these can
+/// not be spoofed
+pub fn decode_inner_instructions(instructions: &[InnerInstruction]) -> Vec<SuperpullEvent> {
+    decode_program_inner_instructions(&superpull_program::ID, instructions)
+}
+
+/// Same as [`decode_inner_instructions`] for a deployment of the program at
+/// `program_id`
+pub fn decode_program_inner_instructions(
+    program_id: &Pubkey,
+    instructions: &[InnerInstruction],
+) -> Vec<SuperpullEvent> {
+    let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id);
+    instructions
+        .iter()
+        .filter(|instruction| {
+            instruction.program_id == *program_id
+                && instruction.accounts.first() == Some(&event_authority)
+        })
+        .filter_map(|instruction| {
+            let data = instruction.data.strip_prefix(EVENT_IX_TAG_LE.as_slice())?;
+            SuperpullEvent::decode(data)
+        })
+        .collect()
+}
@@ -0,0 +1,149 @@
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use superpull_program::utils::events::*;
+
+/// Payload layouts of version 1 that differ from the current ones
+pub mod v1 {
+    use anchor_lang::prelude::*;
+
+    /// `ItemRefunded` before it carried the receipt's price and referral fee
+    #[event]
+    pub struct ItemRefunded {
+        pub version: u8,
+        pub auction: Pubkey,
+        pub bidder: Pubkey,
+        pub item_serial: u64,
+        pub asset_id: Pubkey,
+        pub amount: u64,
+        pub timestamp: i64,
+        pub slot: u64,
+    }
+
+    /// `PhaseFirstBid` under its former name, the layout is unchanged
+    #[event]
+    pub struct PhaseStarted {
+        pub version: u8,
+        pub auction: Pubkey,
+        pub phase_index: u8,
+        pub price: u64,
+        pub timestamp: i64,
+        pub slot: u64,
+    }
+}
+
+macro_rules! superpull_events {
+    (
+        $($event:ident),* $(,)?;
+        $($legacy:ident($legacy_event:ty) as $legacy_name:literal),* $(,)?
+    ) => {
+        /// Every event emitted by the program, with the earlier layouts of
+        /// events that changed
+        #[allow(clippy::large_enum_variant)]
+        pub enum SuperpullEvent {
+            $($event($event),)*
+            $($legacy($legacy_event),)*
+        }
+
+        impl SuperpullEvent {
+            /// Decodes a payload in the current layout of its event
+            fn decode_current(discriminator: &[u8], payload: &[u8]) -> Option<Self> {
+                $(
+                    if discriminator == $event::DISCRIMINATOR {
+                        return $event::try_from_slice(payload).ok().map(Self::$event);
+                    }
+                )*
+                None
+            }
+
+            /// Schema version of the event payload
+            pub fn version(&self) -> u8 {
+                match self {
+                    $(Self::$event(event) => event.version,)*
+                    $(Self::$legacy(event) => event.version,)*
+                }
+            }
+
+            /// Name of the event, as in the IDL
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$event(_) => stringify!($event),)*
+                    $(Self::$legacy(_) => $legacy_name,)*
+                }
+            }
+        }
+    };
+}
+
+impl SuperpullEvent {
+    /// Decodes event data, an 8-byte discriminator followed by the
+    /// Borsh-serialized event. Each schema version is decoded with its own
+    /// layouts; returns `None` for data that is not a known event, including
+    /// payloads of a version newer than `EVENT_VERSION`
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 9 {
+            return None;
+        }
+        let (discriminator, payload) = data.split_at(8);
+        // Every layout starts with its version
+        match payload[0] {
+            1 => Self::decode_v1(discriminator, payload),
+            EVENT_VERSION => Self::decode_current(discriminator, payload),
+            _ => None,
+        }
+    }
+
+    /// Decodes a version 1 payload. Only `ItemRefunded` changed layout in
+    /// version 2, and `PhaseFirstBid` was emitted as `PhaseStarted`
+    fn decode_v1(discriminator: &[u8], payload: &[u8]) -> Option<Self> {
+        if discriminator == v1::ItemRefunded::DISCRIMINATOR {
+            // Builds released before the version bump already added the
+            // price and referral fee under version 1
+            return v1::ItemRefunded::try_from_slice(payload)
+                .ok()
+                .map(Self::ItemRefundedV1)
+                .or_else(|| ItemRefunded::try_from_slice(payload).ok().map(Self::ItemRefunded));
+        }
+        if discriminator == v1::PhaseStarted::DISCRIMINATOR {
+            return PhaseFirstBid::try_from_slice(payload).ok().map(Self::PhaseFirstBid);
+        }
+        Self::decode_current(discriminator, payload)
+    }
+}
+
+superpull_events!(
+    AuctionInitialized,
+    PriceUpdate,
+    BidPlaced,
+    AuctionGraduated,
+    FundsWithdrawn,
+    WithdrawableBalance,
+    PayeePaid,
+    ProtocolFeeCollected,
+    ConfigUpdated,
+    BidRefunded,
+    PhasesConfigured,
+    PhaseFirstBid,
+    ReferrerRegistered,
+    ReferralRewardsClaimed,
+    MilestonesConfigured,
+    MilestoneSubmitted,
+    MilestoneVetoed,
+    AuctionHalted,
+    DeliveryConfirmed,
+    ArbiterRuled,
+    AuthorityProposed,
+    AuthorityTransferred,
+    TreasuryUpdated,
+    AuctionCancelled,
+    AuctionUpdated,
+    AuctionRegistered,
+    RegistryStatusUpdated,
+    ProposalCreated,
+    ProposalApproved,
+    ProposalExecuted,
+    ItemRefunded,
+    AuctionStatusChanged,
+    SellBackConfigured,
+    ItemSoldBack,
+    ItemGifted;
+    ItemRefundedV1(v1::ItemRefunded) as "ItemRefunded",
+);
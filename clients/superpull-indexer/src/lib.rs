@@ -0,0 +1,15 @@
+//! Event decoder and indexer for the SuperPull program: decodes the events
+//! of transactions, emitted through the event self-CPI or logged, and folds
+//! them into a projection of every auction and bidder
+
+pub mod cpi;
+pub mod events;
+pub mod logs;
+pub mod projection;
+pub mod transaction;
+
+pub use cpi::{decode_inner_instructions, decode_program_inner_instructions, InnerInstruction};
+pub use events::SuperpullEvent;
+pub use logs::{decode_logs, decode_program_logs};
+pub use projection::{AuctionProjection, BidderProjection, Projection};
+pub use transaction::IndexedTransaction;
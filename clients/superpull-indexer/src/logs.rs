@@ -0,0 +1,53 @@
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::events::SuperpullEvent;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Decodes the events the program emitted in a transaction's log messages.
+///
+/// Only `Program data:` lines logged while the program is the innermost
+/// invocation are decoded, so data logged by other programs, including
+/// programs the SuperPull program calls, is skipped. A failed transaction
+/// is rolled back and yields no events
+pub fn decode_logs(logs: &[String]) -> Vec<SuperpullEvent> {
+    decode_program_logs(&superpull_program::ID, logs)
+}
+
+/// Same as [`decode_logs`] for a deployment of the program at `program_id`
+pub fn decode_program_logs(program_id: &Pubkey, logs: &[String]) -> Vec<SuperpullEvent> {
+    let program_id = program_id.to_string();
+    let mut invocations: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if invocations.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            if let Some(event) = STANDARD
+                .decode(data)
+                .ok()
+                .and_then(|data| SuperpullEvent::decode(&data))
+            {
+                events.push(event);
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut words = rest.split_whitespace();
+            let (Some(program), Some(status)) = (words.next(), words.next()) else {
+                continue;
+            };
+            match status {
+                "invoke" => invocations.push(program),
+                "success" => {
+                    invocations.pop();
+                }
+                "failed:" => return Vec::new(),
+                _ => {}
+            }
+        }
+    }
+
+    events
+}
@@ -0,0 +1,251 @@
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::{events::SuperpullEvent, transaction::IndexedTransaction};
+
+/// Auction state as rebuilt from its events
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuctionProjection {
+    pub authority: Pubkey,
+    pub auction_id: u64,
+    pub merkle_tree: Pubkey,
+    pub token_mint: Pubkey,
+    pub collection_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub base_price: u64,
+    pub price_increment: u64,
+    pub max_supply: u64,
+    pub minimum_items: u64,
+    pub deadline: i64,
+    pub max_per_wallet: u64,
+    pub current_supply: u64,
+    pub current_price: u64,
+    pub total_value_locked: u64,
+    pub total_raised: u64,
+    pub total_withdrawn: u64,
+    pub total_refunded: u64,
+    pub is_graduated: bool,
+    /// Unix timestamp of the graduating bid, zero until graduation
+    pub graduated_at: i64,
+    pub is_halted: bool,
+    pub registry_status: u8,
+    /// Slot of the last event applied to the auction
+    pub last_slot: u64,
+}
+
+/// A bidder's position in an auction as rebuilt from its events
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BidderProjection {
+    pub amount: u64,
+    pub count: u64,
+    pub referral_fees: u64,
+    pub refunded: u64,
+}
+
+/// In-memory projection of every auction and bidder seen in the applied
+/// transactions. Transactions must be applied in the order they landed
+#[derive(Clone, Debug, Default)]
+pub struct Projection {
+    pub auctions: BTreeMap<Pubkey, AuctionProjection>,
+    pub bidders: BTreeMap<(Pubkey, Pubkey), BidderProjection>,
+    /// Slot of the last applied transaction
+    pub last_slot: u64,
+}
+
+impl Projection {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn auction(&self, auction: &Pubkey) -> Option<&AuctionProjection> {
+        self.auctions.get(auction)
+    }
+
+    pub fn bidder(&self, auction: &Pubkey, bidder: &Pubkey) -> Option<&BidderProjection> {
+        self.bidders.get(&(*auction, *bidder))
+    }
+
+    /// Bidders of an auction with their positions
+    pub fn auction_bidders<'a>(
+        &'a self,
+        auction: &'a Pubkey,
+    ) -> impl Iterator<Item = (&'a Pubkey, &'a BidderProjection)> + 'a {
+        self.bidders
+            .range((*auction, Pubkey::default())..)
+            .take_while(move |((key, _), _)| key == auction)
+            .map(|((_, bidder), position)| (bidder, position))
+    }
+
+    /// Decodes the events of a transaction and applies them, returning the
+    /// number of events applied
+    pub fn apply_transaction(&mut self, transaction: &IndexedTransaction) -> usize {
+        let events = transaction.events();
+        for event in &events {
+            self.apply(transaction.slot, event);
+        }
+        self.last_slot = self.last_slot.max(transaction.slot);
+        events.len()
+    }
+
+    /// Applies a single event. Events of auctions created before the
+    /// indexer started are tracked from the first event seen
+    pub fn apply(&mut self, slot: u64, event: &SuperpullEvent) {
+        match event {
+            SuperpullEvent::AuctionInitialized(event) => {
+                self.auctions.insert(
+                    event.auction,
+                    AuctionProjection {
+                        authority: event.authority,
+                        auction_id: event.auction_id,
+                        merkle_tree: event.merkle_tree,
+                        token_mint: event.token_mint,
+                        collection_mint: event.collection_mint,
+                        treasury: event.treasury,
+                        base_price: event.base_price,
+                        price_increment: event.price_increment,
+                        max_supply: event.max_supply,
+                        minimum_items: event.minimum_items,
+                        deadline: event.deadline,
+                        max_per_wallet: event.max_per_wallet,
+                        current_price: event.base_price,
+                        last_slot: slot,
+                        ..Default::default()
+                    },
+                );
+            }
+            SuperpullEvent::PriceUpdate(event) => {
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.current_price = event.price;
+                auction.current_supply = event.supply;
+            }
+            SuperpullEvent::BidPlaced(event) => {
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.current_supply = event.new_supply;
+                auction.current_price = auction
+                    .base_price
+                    .saturating_add(auction.price_increment.saturating_mul(event.new_supply));
+                auction.total_raised = auction.total_raised.saturating_add(event.amount);
+                auction.total_value_locked = auction
+                    .total_value_locked
+                    .saturating_add(event.amount.saturating_sub(event.referral_fee));
+
+                let bidder = self.bidders.entry((event.auction, event.bidder)).or_default();
+                bidder.amount = bidder.amount.saturating_add(event.amount);
+                bidder.count = bidder.count.saturating_add(1);
+                bidder.referral_fees = bidder.referral_fees.saturating_add(event.referral_fee);
+            }
+            SuperpullEvent::AuctionGraduated(event) => {
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.is_graduated = true;
+                auction.graduated_at = event.graduated_at;
+                auction.current_supply = event.total_items;
+                auction.total_value_locked = event.total_value_locked;
+            }
+            SuperpullEvent::FundsWithdrawn(event) => {
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.total_withdrawn = auction
+                    .total_withdrawn
+                    .saturating_add(event.amount)
+                    .saturating_add(event.protocol_fee);
+                auction.total_value_locked = event.remaining;
+            }
+            SuperpullEvent::BidRefunded(event) => {
+                let is_halted = self.auction_mut(&event.auction, slot).is_halted;
+                let bidder = self.bidders.entry((event.auction, event.bidder)).or_default();
+                // Referral fees leave the TVL of a failed auction with the
+                // refund, a halted auction refunds from the TVL alone
+                let released = if is_halted {
+                    event.amount
+                } else {
+                    bidder.amount.saturating_sub(bidder.referral_fees)
+                };
+                bidder.refunded = bidder.refunded.saturating_add(event.amount);
+                bidder.amount = 0;
+                bidder.referral_fees = 0;
+
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.total_refunded = auction.total_refunded.saturating_add(event.amount);
+                auction.total_value_locked = auction.total_value_locked.saturating_sub(released);
+            }
+            SuperpullEvent::ItemRefunded(event) => {
+                let is_halted = self.auction_mut(&event.auction, slot).is_halted;
+                let bidder = self.bidders.entry((event.auction, event.bidder)).or_default();
+                bidder.amount = bidder.amount.saturating_sub(event.price);
+                bidder.count = bidder.count.saturating_sub(1);
+                bidder.referral_fees = bidder.referral_fees.saturating_sub(event.referral_fee);
+                bidder.refunded = bidder.refunded.saturating_add(event.amount);
+
+                let released = if is_halted {
+                    event.amount
+                } else {
+                    event.price.saturating_sub(event.referral_fee)
+                };
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.total_refunded = auction.total_refunded.saturating_add(event.amount);
+                auction.total_value_locked = auction.total_value_locked.saturating_sub(released);
+            }
+            SuperpullEvent::ItemRefundedV1(event) => {
+                // Version 1 only records the refunded amount, which was the
+                // item's whole contribution to the bid and the TVL
+                let bidder = self.bidders.entry((event.auction, event.bidder)).or_default();
+                bidder.amount = bidder.amount.saturating_sub(event.amount);
+                bidder.count = bidder.count.saturating_sub(1);
+                bidder.refunded = bidder.refunded.saturating_add(event.amount);
+
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.total_refunded = auction.total_refunded.saturating_add(event.amount);
+                auction.total_value_locked = auction.total_value_locked.saturating_sub(event.amount);
+            }
+            SuperpullEvent::ItemSoldBack(event) => {
+                let bidder = self.bidders.entry((event.auction, event.bidder)).or_default();
+                bidder.amount = bidder.amount.saturating_sub(event.price);
+                bidder.count = bidder.count.saturating_sub(1);
+                bidder.referral_fees = bidder.referral_fees.saturating_sub(event.referral_fee);
+
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.current_supply = event.new_supply;
+                auction.current_price = auction
+                    .base_price
+                    .saturating_add(auction.price_increment.saturating_mul(event.new_supply));
+                // Only the bidder's amount leaves the escrow, the referral
+                // fee is no longer owed and stays in the TVL with the spread
+                auction.total_value_locked = auction
+                    .total_value_locked
+                    .saturating_add(event.referral_fee)
+                    .saturating_sub(event.amount);
+            }
+            SuperpullEvent::AuctionHalted(event) => {
+                self.auction_mut(&event.auction, slot).is_halted = true;
+            }
+            SuperpullEvent::AuctionCancelled(event) => {
+                self.auction_mut(&event.auction, slot).is_halted = true;
+            }
+            SuperpullEvent::AuthorityTransferred(event) => {
+                self.auction_mut(&event.auction, slot).authority = event.new_authority;
+            }
+            SuperpullEvent::TreasuryUpdated(event) => {
+                self.auction_mut(&event.auction, slot).treasury = event.treasury;
+            }
+            SuperpullEvent::AuctionUpdated(event) => {
+                let auction = self.auction_mut(&event.auction, slot);
+                auction.deadline = event.deadline;
+                auction.max_per_wallet = event.max_per_wallet;
+                auction.treasury = event.treasury;
+            }
+            SuperpullEvent::RegistryStatusUpdated(event) => {
+                self.auction_mut(&event.auction, slot).registry_status = event.status;
+            }
+            SuperpullEvent::AuctionStatusChanged(event) => {
+                self.auction_mut(&event.auction, slot).registry_status = event.status;
+            }
+            _ => {}
+        }
+    }
+
+    fn auction_mut(&mut self, auction: &Pubkey, slot: u64) -> &mut AuctionProjection {
+        let auction = self.auctions.entry(*auction).or_default();
+        auction.last_slot = auction.last_slot.max(slot);
+        auction
+    }
+}
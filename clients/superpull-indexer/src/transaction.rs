@@ -0,0 +1,25 @@
+use crate::{cpi::decode_inner_instructions, events::SuperpullEvent, logs::decode_logs, InnerInstruction};
+
+/// A confirmed transaction, as fetched from an RPC node or a Geyser stream
+#[derive(Clone, Debug, Default)]
+pub struct IndexedTransaction {
+    pub slot: u64,
+    /// Failed transactions are rolled back and yield no events
+    pub succeeded: bool,
+    pub log_messages: Vec<String>,
+    pub inner_instructions: Vec<InnerInstruction>,
+}
+
+impl IndexedTransaction {
+    /// Events of the transaction, from the event self-CPIs and from the logs
+    /// of programs built with the `log-events` feature. A build emits events
+    /// one way only, so no event is decoded twice
+    pub fn events(&self) -> Vec<SuperpullEvent> {
+        if !self.succeeded {
+            return Vec::new();
+        }
+        let mut events = decode_inner_instructions(&self.inner_instructions);
+        events.extend(decode_logs(&self.log_messages));
+        events
+    }
+}
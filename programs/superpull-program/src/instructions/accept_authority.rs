@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::AuctionState,
+    utils::{errors::SuperpullProgramError, events::{AuthorityTransferred, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        constraint = auction.pending_authority.is_some() @ SuperpullProgramError::NoPendingAuthority,
+        constraint = auction.pending_authority == Some(new_authority.key()) @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// The proposed authority accepting the transfer
+    pub new_authority: Signer<'info>,
+}
+
+pub fn accept_authority_handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+
+    // Pin the PDA seed authority before the stored authority changes
+    auction.creator = *auction.seed_authority();
+
+    let previous_authority = auction.authority;
+    auction.authority = ctx.accounts.new_authority.key();
+    auction.pending_authority = None;
+
+    let clock = Clock::get()?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(AuthorityTransferred {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        previous_authority,
+        new_authority: auction.authority,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionState, Proposal},
+    utils::{errors::SuperpullProgramError, events::{ProposalApproved, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            auction.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        has_one = auction,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// A member of the auction's signer set
+    pub signer: Signer<'info>,
+}
+
+pub fn approve_proposal_handler(ctx: Context<ApproveProposal>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let proposal = &mut ctx.accounts.proposal;
+
+    // Validate proposal state
+    require!(
+        !proposal.executed,
+        SuperpullProgramError::ProposalAlreadyExecuted
+    );
+
+    // Validate signer
+    let signer_index = auction
+        .multisig_signer_index(&ctx.accounts.signer.key())
+        .ok_or(SuperpullProgramError::NotMultisigSigner)?;
+    require!(
+        !proposal.has_approved(signer_index),
+        SuperpullProgramError::ProposalAlreadyApproved
+    );
+
+    proposal.approvals |= 1 << signer_index;
+
+    let clock = Clock::get()?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ProposalApproved {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        proposal: proposal.key(),
+        signer: ctx.accounts.signer.key(),
+        approvals: proposal.approval_count(),
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
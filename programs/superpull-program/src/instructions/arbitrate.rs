@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{ArbiterRuling, AuctionState, RulingRecord},
+    utils::{errors::SuperpullProgramError, events::{ArbiterRuled, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Arbitrate<'info> {
+    #[account(
+        mut,
+        constraint = auction.arbiter == Some(arbiter.key()) @ SuperpullProgramError::UnauthorizedArbiter,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = RulingRecord::LEN,
+        seeds = [
+            b"ruling",
+            auction.key().as_ref(),
+            auction.ruling_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub ruling_record: Account<'info, RulingRecord>,
+
+    /// The arbiter appointed for the auction
+    pub arbiter: Signer<'info>,
+
+    /// The account that will pay for the ruling record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn arbitrate_handler(
+    ctx: Context<Arbitrate>,
+    ruling: ArbiterRuling,
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+
+    // A halted auction is final, its escrow belongs to the refunding bidders
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    let previous_status = auction.registry_status(current_time);
+
+    // Apply the ruling
+    match ruling {
+        ArbiterRuling::Freeze => {
+            auction.withdrawals_frozen = true;
+        }
+        ArbiterRuling::Refund => {
+            auction.is_halted = true;
+            auction.refund_pool = auction.total_value_locked;
+            auction.refund_basis = auction.total_proceeds()?;
+        }
+        ArbiterRuling::Release => {
+            auction.withdrawals_frozen = false;
+            if auction.is_delivery_pending() {
+                auction.delivery_confirmed = true;
+            }
+        }
+    }
+
+    // Record the ruling
+    let ruling_record = &mut ctx.accounts.ruling_record;
+    ruling_record.auction = auction.key();
+    ruling_record.arbiter = ctx.accounts.arbiter.key();
+    ruling_record.index = auction.ruling_count;
+    ruling_record.ruling = ruling;
+    ruling_record.reason_hash = reason_hash;
+    ruling_record.timestamp = current_time;
+    ruling_record.bump = ctx.bumps.ruling_record;
+
+    auction.ruling_count = auction.ruling_count
+        .checked_add(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ArbiterRuled {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        arbiter: ruling_record.arbiter,
+        ruling_index: ruling_record.index,
+        ruling,
+        reason_hash,
+        timestamp: current_time,
+        slot: clock.slot,
+    })?;
+    events.emit_status_change(auction, previous_status, &clock)?;
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::AuctionState,
+    utils::{errors::SuperpullProgramError, events::{AuctionCancelled, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelAuction<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+}
+
+pub fn cancel_auction_handler(ctx: Context<CancelAuction>) -> Result<()> {
+    // Multisig auctions cancel through approved proposals
+    require!(
+        !ctx.accounts.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_cancellation(&mut ctx.accounts.auction, &events)
+}
+
+/// Halts the auction so every bidder can refund their share of the escrow,
+/// shared by `cancel_auction` and approved multisig proposals
+pub fn apply_cancellation(auction: &mut Account<AuctionState>, events: &EventEmitter) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+
+    let previous_status = auction.registry_status(clock.unix_timestamp);
+    auction.is_halted = true;
+    auction.refund_pool = auction.total_value_locked;
+    auction.refund_basis = auction.total_proceeds()?;
+
+    events.emit(AuctionCancelled {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        refund_pool: auction.refund_pool,
+        refund_basis: auction.refund_basis,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+    events.emit_status_change(auction, previous_status, &clock)?;
+
+    Ok(())
+}
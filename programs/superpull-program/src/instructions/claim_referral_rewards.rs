@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::{
+    instructions::transfer_from_auction,
+    state::{AuctionState, ReferrerState},
+    utils::{errors::SuperpullProgramError, events::{ReferralRewardsClaimed, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"referrer",
+            auction.key().as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump = referrer_state.bump,
+        has_one = auction,
+        has_one = referrer,
+    )]
+    pub referrer_state: Account<'info, ReferrerState>,
+
+    pub referrer: Signer<'info>,
+
+    /// The referrer's token account to receive the rewards
+    #[account(
+        mut,
+        token::mint = auction.token_mint,
+        token::authority = referrer,
+    )]
+    pub referrer_token_account: Account<'info, TokenAccount>,
+
+    /// The auction's token account holding the rewards
+    /// CHECK: Validated through token program CPI
+    #[account(mut)]
+    pub auction_token_account: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_referral_rewards_handler(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+
+    // Rewards are only final once the auction graduates and delivery is
+    // confirmed, bidders of a failed auction are refunded in full. Refunds
+    // of a halted auction only cover the net escrow, so rewards stay owed
+    if !auction.is_halted {
+        require!(
+            auction.is_graduated,
+            SuperpullProgramError::NotGraduated
+        );
+        require!(
+            !auction.is_delivery_pending(),
+            SuperpullProgramError::DeliveryNotConfirmed
+        );
+    }
+
+    let amount = ctx.accounts.referrer_state.claimable_rewards();
+    require!(amount > 0, SuperpullProgramError::NoReferralRewards);
+
+    // Transfer rewards from the auction account to the referrer
+    transfer_from_auction(
+        auction,
+        &ctx.accounts.auction_token_account,
+        &ctx.accounts.referrer_token_account.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+
+    // Update state
+    let auction = &mut ctx.accounts.auction;
+    auction.referral_fees_owed = auction.referral_fees_owed
+        .checked_sub(amount)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    let referrer_state = &mut ctx.accounts.referrer_state;
+    referrer_state.claimed_rewards = referrer_state.claimed_rewards
+        .checked_add(amount)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    let clock = Clock::get()?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ReferralRewardsClaimed {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        referrer: referrer_state.referrer,
+        amount,
+        total_rewards: referrer_state.total_rewards,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::AuctionState,
+    utils::{errors::SuperpullProgramError, events::{DeliveryConfirmed, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfirmDelivery<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+}
+
+pub fn confirm_delivery_handler(ctx: Context<ConfirmDelivery>) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+
+    // Validate auction state
+    require!(
+        auction.delivery_period.is_some(),
+        SuperpullProgramError::NoDeliveryGuarantee
+    );
+    require!(
+        auction.is_graduated,
+        SuperpullProgramError::NotGraduated
+    );
+    require!(
+        !auction.delivery_confirmed,
+        SuperpullProgramError::DeliveryAlreadyConfirmed
+    );
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+
+    // Once the deadline has passed bidders may already be refunding
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    require!(
+        !auction.is_delivery_failed(current_time),
+        SuperpullProgramError::DeliveryDeadlinePassed
+    );
+
+    auction.delivery_confirmed = true;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(DeliveryConfirmed {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        authority: ctx.accounts.authority.key(),
+        confirmed_at: current_time,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionState, Proposal, ProposalAction},
+    utils::{errors::SuperpullProgramError, events::{ProposalCreated, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Proposal::LEN,
+        seeds = [
+            b"proposal",
+            auction.key().as_ref(),
+            auction.proposal_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// A member of the auction's signer set, approves the proposal
+    pub proposer: Signer<'info>,
+
+    /// The account that will pay for the proposal account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_proposal_handler(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+    let auction = &mut ctx.accounts.auction;
+
+    // Validate proposer
+    require!(
+        auction.has_multisig(),
+        SuperpullProgramError::NoMultisig
+    );
+    let signer_index = auction
+        .multisig_signer_index(&ctx.accounts.proposer.key())
+        .ok_or(SuperpullProgramError::NotMultisigSigner)?;
+
+    // Store proposal, the proposer approves it
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.auction = auction.key();
+    proposal.index = auction.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.action = action;
+    proposal.approvals = 1 << signer_index;
+    proposal.executed = false;
+    let clock = Clock::get()?;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.bump = ctx.bumps.proposal;
+
+    auction.proposal_count = auction.proposal_count
+        .checked_add(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ProposalCreated {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        proposal: proposal.key(),
+        index: proposal.index,
+        proposer: proposal.proposer,
+        action,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,267 @@
+use anchor_lang::prelude::*;
+use crate::{
+    instructions::{
+        apply_auction_update, apply_authority_proposal, apply_cancellation, apply_milestones,
+        apply_phases, apply_sell_back, withdraw::*,
+    },
+    state::{
+        milestones_hash, phases_hash, AuctionMilestones, AuctionPhases, AuctionState, PhaseArgs,
+        Proposal, ProposalAction,
+    },
+    utils::{errors::SuperpullProgramError, events::{EventEmitter, ProposalExecuted, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            auction.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        has_one = auction,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteWithdrawProposal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            funds.auction.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        constraint = proposal.auction == funds.auction.key() @ SuperpullProgramError::InvalidAccountData,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The approved proposal authorizes the withdrawal in place of the
+    /// authority's signature
+    pub funds: WithdrawFunds<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecutePhasesProposal<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            auction.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        has_one = auction,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AuctionPhases::LEN,
+        seeds = [
+            b"phases",
+            auction.key().as_ref(),
+        ],
+        bump
+    )]
+    pub phases: Account<'info, AuctionPhases>,
+
+    /// The account that will pay for the phases account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteMilestonesProposal<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"proposal",
+            auction.key().as_ref(),
+            proposal.index.to_le_bytes().as_ref(),
+        ],
+        bump = proposal.bump,
+        has_one = auction,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AuctionMilestones::LEN,
+        seeds = [
+            b"milestones",
+            auction.key().as_ref(),
+        ],
+        bump
+    )]
+    pub milestones: Account<'info, AuctionMilestones>,
+
+    /// The account that will pay for the milestones account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Executes an approved cancellation, parameter update, authority nomination
+/// or sell-back configuration
+pub fn execute_proposal_handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+    check_approved(&ctx.accounts.auction, &ctx.accounts.proposal)?;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    match ctx.accounts.proposal.action {
+        ProposalAction::Cancel => apply_cancellation(&mut ctx.accounts.auction, &events)?,
+        ProposalAction::Update { args } => {
+            apply_auction_update(&mut ctx.accounts.auction, args, &events)?
+        }
+        ProposalAction::ProposeAuthority { new_authority } => {
+            apply_authority_proposal(&mut ctx.accounts.auction, new_authority, &events)?
+        }
+        ProposalAction::SetSellBack { spread_bps } => {
+            apply_sell_back(&mut ctx.accounts.auction, spread_bps, &events)?
+        }
+        ProposalAction::Withdraw { .. }
+        | ProposalAction::SetPhases { .. }
+        | ProposalAction::SetMilestones { .. } => {
+            return err!(SuperpullProgramError::InvalidProposalAction);
+        }
+    }
+
+    mark_executed(&ctx.accounts.auction, &mut ctx.accounts.proposal, &events)
+}
+
+/// Executes an approved withdrawal through the regular withdraw transfers.
+///
+/// Remaining accounts: same as `withdraw`
+pub fn execute_withdraw_proposal_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteWithdrawProposal<'info>>,
+) -> Result<()> {
+    check_approved(&ctx.accounts.funds.auction, &ctx.accounts.proposal)?;
+
+    let ProposalAction::Withdraw { amount } = ctx.accounts.proposal.action else {
+        return err!(SuperpullProgramError::InvalidProposalAction);
+    };
+    let events = EventEmitter::new(
+        &ctx.accounts.funds.event_authority,
+        ctx.bumps.funds.event_authority,
+    );
+    process_withdraw(&mut ctx.accounts.funds, ctx.remaining_accounts, amount, &events)?;
+
+    mark_executed(&ctx.accounts.funds.auction, &mut ctx.accounts.proposal, &events)
+}
+
+/// Executes an approved `SetPhases` proposal with the phases it hashed
+pub fn execute_phases_proposal_handler(
+    ctx: Context<ExecutePhasesProposal>,
+    phases: Vec<PhaseArgs>,
+) -> Result<()> {
+    check_approved(&ctx.accounts.auction, &ctx.accounts.proposal)?;
+
+    let ProposalAction::SetPhases { phases_hash: approved } = ctx.accounts.proposal.action else {
+        return err!(SuperpullProgramError::InvalidProposalAction);
+    };
+    require!(
+        phases_hash(&phases)? == approved,
+        SuperpullProgramError::ProposalArgsMismatch
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_phases(
+        &mut ctx.accounts.auction,
+        &mut ctx.accounts.phases,
+        ctx.bumps.phases,
+        phases,
+        &events,
+    )?;
+
+    mark_executed(&ctx.accounts.auction, &mut ctx.accounts.proposal, &events)
+}
+
+/// Executes an approved `SetMilestones` proposal with the milestones it hashed
+pub fn execute_milestones_proposal_handler(
+    ctx: Context<ExecuteMilestonesProposal>,
+    release_bps: Vec<u16>,
+    challenge_period: i64,
+    veto_threshold_bps: u16,
+) -> Result<()> {
+    check_approved(&ctx.accounts.auction, &ctx.accounts.proposal)?;
+
+    let ProposalAction::SetMilestones { milestones_hash: approved } = ctx.accounts.proposal.action
+    else {
+        return err!(SuperpullProgramError::InvalidProposalAction);
+    };
+    require!(
+        milestones_hash(&release_bps, challenge_period, veto_threshold_bps)? == approved,
+        SuperpullProgramError::ProposalArgsMismatch
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_milestones(
+        &mut ctx.accounts.auction,
+        &mut ctx.accounts.milestones,
+        ctx.bumps.milestones,
+        release_bps,
+        challenge_period,
+        veto_threshold_bps,
+        &events,
+    )?;
+
+    mark_executed(&ctx.accounts.auction, &mut ctx.accounts.proposal, &events)
+}
+
+/// Checks the proposal is pending and has reached the approval threshold
+fn check_approved(auction: &AuctionState, proposal: &Proposal) -> Result<()> {
+    require!(
+        auction.has_multisig(),
+        SuperpullProgramError::NoMultisig
+    );
+    require!(
+        !proposal.executed,
+        SuperpullProgramError::ProposalAlreadyExecuted
+    );
+    require!(
+        proposal.approval_count() >= auction.multisig_threshold,
+        SuperpullProgramError::InsufficientApprovals
+    );
+    Ok(())
+}
+
+fn mark_executed(
+    auction: &Account<AuctionState>,
+    proposal: &mut Account<Proposal>,
+    events: &EventEmitter,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    proposal.executed = true;
+
+    events.emit(ProposalExecuted {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        proposal: proposal.key(),
+        index: proposal.index,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
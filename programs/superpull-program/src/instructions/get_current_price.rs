@@ -1,21 +1,47 @@
 use anchor_lang::prelude::*;
-use crate::{state::AuctionState, utils::events::PriceUpdate};
+use crate::{
+    state::{AuctionPhases, AuctionState},
+    utils::{errors::SuperpullProgramError, events::{PriceUpdate, EventEmitter, EVENT_VERSION}},
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct GetCurrentPrice<'info> {
     pub auction: Account<'info, AuctionState>,
+
+    /// Sale phases, required when the auction has phases
+    #[account(
+        seeds = [b"phases", auction.key().as_ref()],
+        bump = phases.bump,
+    )]
+    pub phases: Option<Account<'info, AuctionPhases>>,
 }
 
 pub fn get_current_price_handler(ctx: Context<GetCurrentPrice>) -> Result<()> {
+    let clock = Clock::get()?;
     let auction = &ctx.accounts.auction;
-    let current_price = auction.base_price + (auction.price_increment * auction.current_supply);
+    let current_price = if auction.has_phases {
+        let phases = ctx.accounts.phases
+            .as_ref()
+            .ok_or(SuperpullProgramError::PhasesAccountRequired)?;
+        let index = phases
+            .active_phase_index(clock.unix_timestamp)
+            .ok_or(SuperpullProgramError::NoActivePhase)?;
+        phases.phases[index].current_price()?
+    } else {
+        auction.current_price()?
+    };
 
     // Emit an event with the current price
-    emit!(PriceUpdate {
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(PriceUpdate {
+        version: EVENT_VERSION,
         auction: auction.key(),
         price: current_price,
         supply: auction.current_supply,
-    });
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
 
     Ok(())
 } 
\ No newline at end of file
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionMilestones, AuctionState},
+    utils::{errors::SuperpullProgramError, events::{WithdrawableBalance, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GetWithdrawable<'info> {
+    pub auction: Account<'info, AuctionState>,
+
+    /// Release milestones, required when the auction has milestones
+    #[account(
+        seeds = [b"milestones", auction.key().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Account<'info, AuctionMilestones>>,
+}
+
+pub fn get_withdrawable_handler(ctx: Context<GetWithdrawable>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let withdrawable = withdrawable_amount(auction, ctx.accounts.milestones.as_deref(), now)?;
+
+    // Emit an event with the vesting position of the seller's proceeds
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(WithdrawableBalance {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        total_proceeds: auction.total_proceeds()?,
+        vested: auction.vested_amount(now)?,
+        withdrawn: auction.total_withdrawn,
+        withdrawable,
+        remaining: auction.total_value_locked,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
+
+/// Unlocked proceeds the seller can withdraw at `now`, nothing can be
+/// withdrawn before delivery is confirmed, while the arbiter froze
+/// withdrawals or once the auction is halted
+pub fn withdrawable_amount(
+    auction: &AuctionState,
+    milestones: Option<&AuctionMilestones>,
+    now: i64,
+) -> Result<u64> {
+    if auction.is_halted || auction.withdrawals_frozen || auction.is_delivery_pending() {
+        return Ok(0);
+    }
+    if auction.has_milestones {
+        let milestones = milestones.ok_or(SuperpullProgramError::MilestonesAccountRequired)?;
+        milestones.withdrawable_amount(auction, now)
+    } else {
+        auction.withdrawable_amount(now)
+    }
+}
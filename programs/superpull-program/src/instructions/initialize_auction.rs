@@ -1,14 +1,36 @@
 use anchor_lang::prelude::*;
 use crate::{
-    state::AuctionState,
-    utils::{errors::SuperpullProgramError, events::AuctionInitialized},
+    state::{
+        validate_multisig, validate_payees, AllowlistConfig, AuctionState, GateRule,
+        GlobalConfig, Payee, Registry, RegistryEntry, RegistryPage, RegistryPosition,
+        SellerCounter, VestingSchedule, MAX_REFERRAL_BPS, REGISTRY_STATUS_ACTIVE,
+    },
+    utils::{
+        errors::SuperpullProgramError,
+        events::{AuctionInitialized, AuctionRegistered, EventEmitter, EVENT_VERSION},
+    },
 };
 
 // use mpl_bubblegum::instructions::{MintToCollectionV1Cpi}
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(base_price: u64, price_increment: u64, max_supply: u64)]
 pub struct InitializeAuction<'info> {
+    /// The authority who will manage the auction, signs so that only it can
+    /// take its auction ids and registry pages
+    pub authority: Signer<'info>,
+
+    /// Hands out the authority's next auction id
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = SellerCounter::LEN,
+        seeds = [b"seller", authority.key().as_ref()],
+        bump
+    )]
+    pub seller_counter: Account<'info, SellerCounter>,
+
     #[account(
         init,
         payer = payer,
@@ -17,11 +39,67 @@ pub struct InitializeAuction<'info> {
             b"auction",
             authority.key().as_ref(),
             collection_mint.key().as_ref(),
+            seller_counter.next_auction_id().to_le_bytes().as_ref(),
         ],
         bump
     )]
     pub auction: Account<'info, AuctionState>,
 
+    /// Registry of every auction
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Registry::LEN,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub global_registry: Box<Account<'info, Registry>>,
+
+    /// Page of the global registry the auction is appended to
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RegistryPage::LEN,
+        seeds = [
+            b"registry_page",
+            global_registry.key().as_ref(),
+            global_registry.current_page().to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub global_registry_page: Box<Account<'info, RegistryPage>>,
+
+    /// Registry of the authority's auctions
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Registry::LEN,
+        seeds = [b"registry", authority.key().as_ref()],
+        bump
+    )]
+    pub authority_registry: Box<Account<'info, Registry>>,
+
+    /// Page of the authority's registry the auction is appended to
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = RegistryPage::LEN,
+        seeds = [
+            b"registry_page",
+            authority_registry.key().as_ref(),
+            authority_registry.current_page().to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub authority_registry_page: Box<Account<'info, RegistryPage>>,
+
+    /// Protocol configuration, fixes the fee and allowed payment mints
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, GlobalConfig>>,
+
     /// CHECK: Validated by Bubblegum program
     pub merkle_tree: AccountInfo<'info>,
 
@@ -33,11 +111,6 @@ pub struct InitializeAuction<'info> {
     /// CHECK: Just storing this pubkey
     pub token_mint: AccountInfo<'info>,
 
-    /// The authority who will manage the auction (doesn't need to be signer)
-    /// CHECK: Just storing this pubkey
-    #[account()]
-    pub authority: AccountInfo<'info>,
-
     /// The account that will pay for the initialization
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -47,6 +120,7 @@ pub struct InitializeAuction<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_auction_handler(
     ctx: Context<InitializeAuction>,
     base_price: u64,
@@ -54,6 +128,18 @@ pub fn initialize_auction_handler(
     max_supply: u64,
     minimum_items: u64,
     deadline: i64,
+    max_per_wallet: u64,
+    allowlist: Option<AllowlistConfig>,
+    gate: Option<GateRule>,
+    payees: Vec<Payee>,
+    dust_payee_index: u8,
+    referral_bps: u16,
+    vesting: Option<VestingSchedule>,
+    delivery_period: Option<i64>,
+    arbiter: Option<Pubkey>,
+    treasury: Option<Pubkey>,
+    multisig_signers: Vec<Pubkey>,
+    multisig_threshold: u8,
 ) -> Result<()> {
     // Validate input parameters
     require!(base_price > 0, SuperpullProgramError::InvalidBasePrice);
@@ -63,14 +149,84 @@ pub fn initialize_auction_handler(
         minimum_items > 0 && minimum_items <= max_supply,
         SuperpullProgramError::InvalidMinimumItems
     );
+    // A zero limit means bids are not capped per wallet
+    require!(
+        max_per_wallet <= max_supply,
+        SuperpullProgramError::InvalidMaxPerWallet
+    );
 
     // Validate deadline is in the future
-    let current_time = Clock::get()?.unix_timestamp;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
     require!(
         deadline > current_time,
         SuperpullProgramError::InvalidDeadline
     );
 
+    // Validate allowlist presale window and price
+    if let Some(allowlist) = allowlist {
+        require!(
+            allowlist.end_time > current_time && allowlist.end_time <= deadline,
+            SuperpullProgramError::InvalidAllowlistConfig
+        );
+        require!(
+            allowlist.price != Some(0),
+            SuperpullProgramError::InvalidAllowlistConfig
+        );
+    }
+
+    // Validate token gate
+    if let Some(GateRule::TokenBalance { min_amount, .. }) = gate {
+        require!(min_amount > 0, SuperpullProgramError::InvalidGateConfig);
+    }
+
+    // Validate referral fee
+    require!(
+        referral_bps <= MAX_REFERRAL_BPS,
+        SuperpullProgramError::InvalidReferralFee
+    );
+
+    // Validate vesting schedule
+    if let Some(vesting) = vesting {
+        require!(
+            vesting.duration > 0 && vesting.cliff >= 0 && vesting.cliff <= vesting.duration,
+            SuperpullProgramError::InvalidVestingSchedule
+        );
+    }
+
+    // Validate delivery guarantee
+    if let Some(period) = delivery_period {
+        require!(period > 0, SuperpullProgramError::InvalidDeliveryPeriod);
+    }
+
+    // Validate arbiter
+    if let Some(arbiter) = arbiter {
+        require!(
+            !arbiter.eq(&Pubkey::default()),
+            SuperpullProgramError::InvalidAuthority
+        );
+    }
+
+    // Validate treasury, the authority receives the proceeds when unset
+    if let Some(treasury) = treasury {
+        require!(
+            !treasury.eq(&Pubkey::default()),
+            SuperpullProgramError::InvalidTreasury
+        );
+    }
+
+    // Validate multisig signer set
+    validate_multisig(&multisig_signers, multisig_threshold)?;
+
+    // Validate revenue split
+    validate_payees(&payees, dust_payee_index)?;
+
+    // Validate payment mint against the protocol allow list
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.token_mint.key()),
+        SuperpullProgramError::MintNotAllowed
+    );
+
     // Validate merkle tree configuration
     require!(
         !ctx.accounts.merkle_tree.data_is_empty(),
@@ -83,6 +239,32 @@ pub fn initialize_auction_handler(
         SuperpullProgramError::InvalidAuthority
     );
 
+    // Take the authority's next auction id
+    let auction_id = ctx.accounts.seller_counter.next_auction_id();
+    let seller_counter = &mut ctx.accounts.seller_counter;
+    seller_counter.authority = ctx.accounts.authority.key();
+    seller_counter.auction_count = auction_id;
+    seller_counter.bump = ctx.bumps.seller_counter;
+
+    // List the auction in the global and the authority's registry
+    let auction_key = ctx.accounts.auction.key();
+    let global_index = append_to_registry(
+        &mut ctx.accounts.global_registry,
+        ctx.bumps.global_registry,
+        Pubkey::default(),
+        &mut ctx.accounts.global_registry_page,
+        ctx.bumps.global_registry_page,
+        auction_key,
+    )?;
+    let authority_index = append_to_registry(
+        &mut ctx.accounts.authority_registry,
+        ctx.bumps.authority_registry,
+        ctx.accounts.authority.key(),
+        &mut ctx.accounts.authority_registry_page,
+        ctx.bumps.authority_registry_page,
+        auction_key,
+    )?;
+
     // Initialize auction state
     let auction = &mut ctx.accounts.auction;
     auction.authority = ctx.accounts.authority.key();
@@ -98,11 +280,45 @@ pub fn initialize_auction_handler(
     auction.deadline = deadline;
     auction.is_graduated = false;
     auction.bump = ctx.bumps.auction;
+    auction.max_per_wallet = max_per_wallet;
+    auction.allowlist = allowlist;
+    auction.gate = gate;
+    auction.protocol_fee_bps = ctx.accounts.config.protocol_fee_bps;
+    auction.payees = payees;
+    auction.dust_payee_index = dust_payee_index;
+    auction.referral_bps = referral_bps;
+    auction.referral_fees_owed = 0;
+    auction.vesting = vesting;
+    auction.graduated_at = 0;
+    auction.total_withdrawn = 0;
+    auction.has_milestones = false;
+    auction.is_halted = false;
+    auction.refund_pool = 0;
+    auction.refund_basis = 0;
+    auction.delivery_period = delivery_period;
+    auction.delivery_confirmed = false;
+    auction.arbiter = arbiter;
+    auction.withdrawals_frozen = false;
+    auction.ruling_count = 0;
+    auction.creator = ctx.accounts.authority.key();
+    auction.pending_authority = None;
+    auction.treasury = treasury.unwrap_or(auction.authority);
+    auction.multisig_signers = multisig_signers;
+    auction.multisig_threshold = multisig_threshold;
+    auction.proposal_count = 0;
+    auction.auction_id = auction_id;
+    auction.registry_position = Some(RegistryPosition {
+        global_index,
+        authority_index,
+    });
 
     // Emit initialization event
-    emit!(AuctionInitialized {
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(AuctionInitialized {
+        version: EVENT_VERSION,
         auction: auction.key(),
         authority: ctx.accounts.authority.key(),
+        auction_id,
         merkle_tree: ctx.accounts.merkle_tree.key(),
         token_mint: ctx.accounts.token_mint.key(),
         collection_mint: ctx.accounts.collection_mint.key(),
@@ -111,7 +327,64 @@ pub fn initialize_auction_handler(
         max_supply,
         minimum_items,
         deadline,
-    });
+        max_per_wallet,
+        allowlist,
+        gate,
+        protocol_fee_bps: auction.protocol_fee_bps,
+        payees: auction.payees.clone(),
+        dust_payee_index,
+        referral_bps,
+        vesting,
+        delivery_period,
+        arbiter,
+        treasury: auction.treasury,
+        multisig_signers: auction.multisig_signers.clone(),
+        multisig_threshold,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    events.emit(AuctionRegistered {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        authority: auction.authority,
+        global_index,
+        authority_index,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
 
     Ok(())
+}
+
+/// Appends `auction` to the registry's current page, setting up freshly
+/// created accounts, and returns its index in the registry
+fn append_to_registry(
+    registry: &mut Account<Registry>,
+    registry_bump: u8,
+    authority: Pubkey,
+    page: &mut Account<RegistryPage>,
+    page_bump: u8,
+    auction: Pubkey,
+) -> Result<u64> {
+    let index = registry.auction_count;
+    if registry.auction_count == 0 {
+        registry.authority = authority;
+        registry.bump = registry_bump;
+    }
+    if page.entries.is_empty() {
+        page.registry = registry.key();
+        page.index = registry.current_page();
+        page.bump = page_bump;
+    }
+
+    page.entries.push(RegistryEntry {
+        auction,
+        status: REGISTRY_STATUS_ACTIVE,
+    });
+    registry.auction_count = index
+        .checked_add(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    Ok(index)
 } 
\ No newline at end of file
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use crate::{
+    program::SuperpullProgram,
+    state::{GlobalConfig, MAX_ALLOWED_MINTS, MAX_PROTOCOL_FEE_BPS},
+    utils::{errors::SuperpullProgramError, events::{ConfigUpdated, EventEmitter, EVENT_AUTHORITY_SEED, EVENT_VERSION}},
+};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = GlobalConfig::LEN,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    /// The program upgrade authority, becomes the config admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The wallet that will receive protocol fees
+    /// CHECK: Just storing this pubkey
+    pub treasury: AccountInfo<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, SuperpullProgram>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ SuperpullProgramError::InvalidAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Signs the event self-CPI, declared by hand since the instruction
+    /// already takes the `program` account `#[event_cpi]` would add
+    /// CHECK: Only the event authority can invoke self-CPI
+    #[account(seeds = [EVENT_AUTHORITY_SEED], bump)]
+    pub event_authority: AccountInfo<'info>,
+}
+
+pub fn initialize_config_handler(
+    ctx: Context<InitializeConfig>,
+    protocol_fee_bps: u16,
+    allowed_mints: Vec<Pubkey>,
+) -> Result<()> {
+    // Validate input parameters
+    require!(
+        protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+        SuperpullProgramError::InvalidProtocolFee
+    );
+    require!(
+        allowed_mints.len() <= MAX_ALLOWED_MINTS,
+        SuperpullProgramError::TooManyAllowedMints
+    );
+    require!(
+        !ctx.accounts.treasury.key().eq(&Pubkey::default()),
+        SuperpullProgramError::InvalidAuthority
+    );
+
+    // Initialize config
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.treasury = ctx.accounts.treasury.key();
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.allowed_mints = allowed_mints;
+    config.bump = ctx.bumps.config;
+
+    let clock = Clock::get()?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ConfigUpdated {
+        version: EVENT_VERSION,
+        admin: config.admin,
+        treasury: config.treasury,
+        protocol_fee_bps: config.protocol_fee_bps,
+        allowed_mints: config.allowed_mints.clone(),
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::{prelude::*, system_program, Discriminator};
+use crate::{
+    state::{AuctionState, BidState},
+    utils::errors::SuperpullProgramError,
+};
+
+#[derive(Accounts)]
+pub struct MigrateAuction<'info> {
+    /// The auction account written with an older layout
+    /// CHECK: Cannot be deserialized until migrated, validated in handler
+    #[account(mut, owner = crate::ID @ SuperpullProgramError::InvalidAccountOwner)]
+    pub auction: UncheckedAccount<'info>,
+
+    /// The account that will pay for the additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBid<'info> {
+    /// The bid account written with an older layout
+    /// CHECK: Cannot be deserialized until migrated, validated in handler
+    #[account(mut, owner = crate::ID @ SuperpullProgramError::InvalidAccountOwner)]
+    pub bid: UncheckedAccount<'info>,
+
+    /// The account that will pay for the additional rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_auction_handler(ctx: Context<MigrateAuction>) -> Result<()> {
+    let auction = ctx.accounts.auction.to_account_info();
+    grow_account(
+        &auction,
+        AuctionState::DISCRIMINATOR,
+        AuctionState::LEN,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    // Make sure the grown account decodes with the current layout
+    AuctionState::try_deserialize(&mut &auction.try_borrow_data()?[..])?;
+
+    Ok(())
+}
+
+pub fn migrate_bid_handler(ctx: Context<MigrateBid>) -> Result<()> {
+    // The legacy `count: u8` is the last field, so zero-extending it yields
+    // the same value as a little-endian `u64`
+    let bid = ctx.accounts.bid.to_account_info();
+    grow_account(
+        &bid,
+        BidState::DISCRIMINATOR,
+        BidState::LEN,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    // Make sure the grown account decodes with the current layout
+    BidState::try_deserialize(&mut &bid.try_borrow_data()?[..])?;
+
+    Ok(())
+}
+
+/// Grows a program account to `new_len`, topping up rent from `payer`.
+/// Fields are only ever appended, and the new bytes are zeroed, which
+/// decodes as the disabled/default value of every new field.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    discriminator: [u8; 8],
+    new_len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    // Validate account type
+    require!(
+        account.data_len() >= 8 && account.try_borrow_data()?[..8] == discriminator,
+        SuperpullProgramError::InvalidAccountData
+    );
+    require!(
+        account.data_len() < new_len,
+        SuperpullProgramError::AlreadyMigrated
+    );
+
+    // Top up rent for the new size
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let shortfall = required_lamports.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, shortfall)?;
+    }
+
+    account.realloc(new_len, true)?;
+
+    Ok(())
+}
@@ -1,11 +1,61 @@
+pub mod initialize_config;
+pub mod update_config;
 pub mod initialize_auction;
 pub mod get_current_price;
 pub mod place_bid;
+pub mod register_referrer;
+pub mod claim_referral_rewards;
+pub mod arbitrate;
+pub mod confirm_delivery;
+pub mod get_withdrawable;
 pub mod withdraw;
 pub mod refund;
+pub mod refund_item;
+pub mod refund_batch;
+pub mod sell_back;
+pub mod migrate;
+pub mod set_phases;
+pub mod set_milestones;
+pub mod submit_milestone;
+pub mod sync_registry;
+pub mod veto_milestone;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod set_treasury;
+pub mod set_sell_back;
+pub mod update_auction;
+pub mod cancel_auction;
+pub mod create_proposal;
+pub mod approve_proposal;
+pub mod execute_proposal;
 
+pub use initialize_config::*;
+pub use update_config::*;
 pub use initialize_auction::*;
 pub use get_current_price::*;
 pub use place_bid::*;
+pub use register_referrer::*;
+pub use claim_referral_rewards::*;
+pub use arbitrate::*;
+pub use confirm_delivery::*;
+pub use get_withdrawable::*;
 pub use withdraw::*;
-pub use refund::*; 
\ No newline at end of file
+pub use refund::*;
+pub use refund_item::*;
+pub use refund_batch::*;
+pub use sell_back::*;
+pub use migrate::*;
+pub use set_phases::*;
+pub use set_milestones::*;
+pub use submit_milestone::*;
+pub use veto_milestone::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use set_treasury::*;
+pub use set_sell_back::*;
+pub use update_auction::*;
+pub use cancel_auction::*;
+pub use create_proposal::*;
+pub use approve_proposal::*;
+pub use execute_proposal::*;
+pub use sync_registry::*;
\ No newline at end of file
@@ -1,20 +1,38 @@
-use anchor_lang::prelude::*;
-use anchor_spl::token;
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{metadata::MetadataAccount, token};
 use mpl_bubblegum::{
+    accounts::TreeConfig,
     instructions::{MintToCollectionV1Cpi, MintToCollectionV1CpiAccounts, MintToCollectionV1InstructionArgs},
     types::{Collection, MetadataArgs, TokenProgramVersion, TokenStandard},
+    utils::get_asset_id,
 };
 use crate::{
-    state::{AuctionState, BidState},
+    state::{fee_amount, AuctionPhases, AuctionState, BidState, GateMarker, GateRule, ItemReceipt, ReferrerState},
     utils::errors::SuperpullProgramError,
-    utils::events::{BidPlaced, AuctionGraduated},
+    utils::events::{BidPlaced, AuctionGraduated, EventEmitter, ItemGifted, PhaseFirstBid, EVENT_VERSION},
+    utils::merkle::{allowlist_leaf, verify_proof},
 };
 
+/// Proof that the bidder is on the auction's allowlist
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct AllowlistProof {
+    /// Number of items the bidder may buy during the presale
+    pub allocation: u64,
+    /// Sibling hashes from the leaf up to the merkle root
+    pub proof: Vec<[u8; 32]>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(amount: u64)]
 pub struct PlaceBid<'info> {
     #[account(mut,
-    seeds = [b"auction", auction.authority.as_ref(), auction.collection_mint.as_ref()],
+    seeds = [
+        b"auction",
+        auction.seed_authority().as_ref(),
+        auction.collection_mint.as_ref(),
+        auction.auction_id_seed().as_ref(),
+    ],
     bump = auction.bump,
     )]
     pub auction: Account<'info, AuctionState>,
@@ -32,12 +50,52 @@ pub struct PlaceBid<'info> {
     )]
     pub bid: Account<'info, BidState>,
 
+    /// Receipt of the item bought by this bid
+    #[account(
+        init,
+        payer = payer,
+        space = ItemReceipt::LEN,
+        seeds = [
+            b"receipt",
+            auction.key().as_ref(),
+            auction.next_item_serial().to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub receipt: Box<Account<'info, ItemReceipt>>,
+
+    /// Sale phases, required when the auction has phases
+    #[account(
+        mut,
+        seeds = [b"phases", auction.key().as_ref()],
+        bump = phases.bump,
+    )]
+    pub phases: Option<Account<'info, AuctionPhases>>,
+
+    /// The promoter who referred this bid, if any
+    #[account(
+        mut,
+        seeds = [
+            b"referrer",
+            auction.key().as_ref(),
+            referrer_state.referrer.as_ref(),
+        ],
+        bump = referrer_state.bump,
+        constraint = referrer_state.auction == auction.key() @ SuperpullProgramError::InvalidAccountData,
+    )]
+    pub referrer_state: Option<Account<'info, ReferrerState>>,
+
     #[account(mut)]
     pub bidder: Signer<'info>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
 
+    /// Owner of the minted item when it is a gift, the bidder still holds
+    /// the bid and is refunded
+    /// CHECK: Any wallet or program PDA can receive the item
+    pub recipient: Option<UncheckedAccount<'info>>,
+
     /// The bidder's token account to transfer from
     /// CHECK: Validated through token program CPI and constraint
     #[account(
@@ -103,9 +161,10 @@ pub struct PlaceBid<'info> {
     pub token_program: AccountInfo<'info>,
 }
 
-pub fn place_bid_handler(
-    ctx: Context<PlaceBid>,
+pub fn place_bid_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
     amount: u64,
+    allowlist_proof: Option<AllowlistProof>,
 ) -> Result<()> {
     // Validate bid amount
     require!(amount > 0, SuperpullProgramError::InvalidBidAmount);
@@ -114,29 +173,102 @@ pub fn place_bid_handler(
         SuperpullProgramError::InvalidBidder
     );
 
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
     let auction = &ctx.accounts.auction;
     
     // Check if auction has expired
-    let current_time = Clock::get()?.unix_timestamp;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
     require!(
         current_time <= auction.deadline || auction.current_supply >= auction.minimum_items,
         SuperpullProgramError::AuctionExpired
     );
     
+    // Halted auctions only process refunds
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+    require!(
+        !auction.is_delivery_failed(current_time),
+        SuperpullProgramError::DeliveryDeadlinePassed
+    );
+
     // Check supply limit
     require!(
         auction.current_supply < auction.max_supply,
         SuperpullProgramError::MaxSupplyReached
     );
+    let previous_status = auction.registry_status(current_time);
+
+    // Check per-wallet limit (zero means unlimited)
+    require!(
+        auction.max_per_wallet == 0 || ctx.accounts.bid.count < auction.max_per_wallet,
+        SuperpullProgramError::WalletLimitReached
+    );
+
+    // Check token gate against the remaining accounts
+    if let Some(gate) = auction.gate {
+        check_gate(
+            gate,
+            &auction.key(),
+            &ctx.accounts.bidder.key(),
+            ctx.remaining_accounts,
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
 
     // Calculate current price
-    let current_price = auction.base_price
-        .checked_add(
-            auction.price_increment
-                .checked_mul(auction.current_supply)
-                .ok_or(SuperpullProgramError::MathOverflow)?
-        )
-        .ok_or(SuperpullProgramError::MathOverflow)?;
+    let mut current_price = auction.current_price()?;
+
+    // During the presale only allowlisted bidders can bid, within their allocation
+    let allowlist = auction.active_allowlist(current_time);
+    if let Some(allowlist) = allowlist {
+        verify_allowlist(
+            &ctx.accounts.bidder.key(),
+            allowlist.merkle_root,
+            allowlist_proof.as_ref(),
+            ctx.accounts.bid.allowlist_used,
+        )?;
+        if let Some(price) = allowlist.price {
+            current_price = price;
+        }
+    }
+
+    // When phases are configured, the active phase sets the price and limits
+    let phase_index = if auction.has_phases {
+        let phases = ctx.accounts.phases
+            .as_ref()
+            .ok_or(SuperpullProgramError::PhasesAccountRequired)?;
+        let index = phases
+            .active_phase_index(current_time)
+            .ok_or(SuperpullProgramError::NoActivePhase)?;
+        let phase = &phases.phases[index];
+        require!(
+            phase.minted < phase.supply,
+            SuperpullProgramError::PhaseSupplyReached
+        );
+
+        let phase_count = ctx.accounts.bid.phase_count_for(index);
+        require!(
+            phase.max_per_wallet == 0 || phase_count < phase.max_per_wallet,
+            SuperpullProgramError::PhaseWalletLimitReached
+        );
+        if let Some(merkle_root) = phase.merkle_root {
+            verify_allowlist(
+                &ctx.accounts.bidder.key(),
+                merkle_root,
+                allowlist_proof.as_ref(),
+                phase_count,
+            )?;
+        }
+
+        current_price = phase.current_price()?;
+        Some(index)
+    } else {
+        None
+    };
 
     // Validate bid amount against current price
     require!(
@@ -144,6 +276,23 @@ pub fn place_bid_handler(
         SuperpullProgramError::InsufficientBidAmount
     );
 
+    // Split the referral fee from the bid, it is held in escrow until claimed
+    let referrer = ctx.accounts.referrer_state.as_ref().map(|state| state.referrer);
+    let referral_fee = match referrer {
+        Some(referrer) => {
+            require!(
+                referrer != ctx.accounts.bidder.key(),
+                SuperpullProgramError::SelfReferral
+            );
+            fee_amount(amount, auction.referral_bps)
+                .ok_or(SuperpullProgramError::MathOverflow)?
+        }
+        None => 0,
+    };
+    let net_amount = amount
+        .checked_sub(referral_fee)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
     // Transfer tokens from bidder to auction account
     let cpi_accounts = token::Transfer {
         from: ctx.accounts.bidder_token_account.to_account_info(),
@@ -158,6 +307,8 @@ pub fn place_bid_handler(
 
     // Update auction state
     let auction = &mut ctx.accounts.auction;
+    let item_serial = auction.next_item_serial();
+    auction.last_item_serial = item_serial;
     
     // Safe arithmetic operations
     auction.current_supply = auction.current_supply
@@ -165,32 +316,98 @@ pub fn place_bid_handler(
         .ok_or(SuperpullProgramError::MathOverflow)?;
     
     auction.total_value_locked = auction.total_value_locked
-        .checked_add(amount)
+        .checked_add(net_amount)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    auction.referral_fees_owed = auction.referral_fees_owed
+        .checked_add(referral_fee)
         .ok_or(SuperpullProgramError::MathOverflow)?;
 
     // Update bid state
     let bid = &mut ctx.accounts.bid;
     bid.auction = auction.key();
     bid.bidder = ctx.accounts.bidder.key();
-    bid.amount += amount;
-    bid.count += 1;
+    bid.amount = bid.amount
+        .checked_add(amount)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.count = bid.count
+        .checked_add(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.open_receipts = bid.open_receipts
+        .checked_add(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.referral_fees = bid.referral_fees
+        .checked_add(referral_fee)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    if allowlist.is_some() {
+        bid.allowlist_used = bid.allowlist_used
+            .checked_add(1)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+    }
+
+    // Update phase state
+    if let (Some(index), Some(phases)) = (phase_index, ctx.accounts.phases.as_mut()) {
+        bid.phase_count = bid.phase_count_for(index)
+            .checked_add(1)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        bid.phase_index = index as u8;
+
+        let phase = &mut phases.phases[index];
+        phase.minted = phase.minted
+            .checked_add(1)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        if !phase.has_bids {
+            phase.has_bids = true;
+            events.emit(PhaseFirstBid {
+                version: EVENT_VERSION,
+                auction: auction.key(),
+                phase_index: index as u8,
+                start_time: phase.start_time,
+                end_time: phase.end_time,
+                base_price: phase.base_price,
+                price_increment: phase.price_increment,
+                supply: phase.supply,
+                timestamp: clock.unix_timestamp,
+                slot: clock.slot,
+            })?;
+        }
+    }
     bid.bump = ctx.bumps.bid;
 
+    // Update referrer totals
+    if let Some(referrer_state) = ctx.accounts.referrer_state.as_mut() {
+        referrer_state.referral_count = referrer_state.referral_count
+            .checked_add(1)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        referrer_state.total_volume = referrer_state.total_volume
+            .checked_add(amount)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        referrer_state.total_rewards = referrer_state.total_rewards
+            .checked_add(referral_fee)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+    }
 
     // Check for graduation
     if !auction.is_graduated && auction.current_supply >= auction.minimum_items {
         auction.is_graduated = true;
-        emit!(AuctionGraduated {
+        auction.graduated_at = current_time;
+        events.emit(AuctionGraduated {
+            version: EVENT_VERSION,
             auction: auction.key(),
             total_items: auction.current_supply,
             total_value_locked: auction.total_value_locked,
-        });
+            graduated_at: auction.graduated_at,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        })?;
     }
 
     // Create bindings for all account_infos to extend their lifetimes
     let bubblegum_program = ctx.accounts.bubblegum_program.to_account_info();
     let tree_config = ctx.accounts.tree_config.to_account_info();
-    let bidder = ctx.accounts.bidder.to_account_info();
+    let leaf_owner = match ctx.accounts.recipient.as_ref() {
+        Some(recipient) => recipient.to_account_info(),
+        None => ctx.accounts.bidder.to_account_info(),
+    };
     let merkle_tree = ctx.accounts.merkle_tree.to_account_info();
     let payer = ctx.accounts.payer.to_account_info();
     let auction_account = auction.to_account_info();
@@ -208,11 +425,11 @@ pub fn place_bid_handler(
         bubblegum_program.as_ref(),
         MintToCollectionV1CpiAccounts {
             tree_config: tree_config.as_ref(),
-            leaf_owner: bidder.as_ref(),
-            leaf_delegate: bidder.as_ref(),
+            leaf_owner: leaf_owner.as_ref(),
+            leaf_delegate: leaf_owner.as_ref(),
             merkle_tree: merkle_tree.as_ref(),
             payer: payer.as_ref(),
-            tree_creator_or_delegate: &auction_account.as_ref(),
+            tree_creator_or_delegate: auction_account.as_ref(),
             collection_authority: auction_account.as_ref(),
             collection_mint: collection_mint.as_ref(),
             collection_metadata: collection_metadata.as_ref(),
@@ -245,24 +462,242 @@ pub fn place_bid_handler(
         },
     );
 
-    // Define signer seeds
-    let signer_seeds: &[&[&[u8]]] = &[&[
-        b"auction",
-        auction.authority.as_ref(),
-        auction.collection_mint.as_ref(),
-        &[auction.bump],
-    ]];
+    // The new leaf takes the next index of the tree, which identifies the asset
+    let leaf_index = next_leaf_index(&tree_config)?;
 
     // Invoke CPI with signed seeds
-    mint_to_collection_cpi.invoke_signed(signer_seeds)?;
+    auction.with_signer_seeds(|signer_seeds| mint_to_collection_cpi.invoke_signed(signer_seeds))?;
+
+    // Record the item
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.auction = auction.key();
+    receipt.serial = item_serial;
+    receipt.bidder = ctx.accounts.bidder.key();
+    receipt.price = amount;
+    receipt.referral_fee = referral_fee;
+    receipt.timestamp = current_time;
+    receipt.leaf_index = leaf_index;
+    receipt.asset_id = get_asset_id(&merkle_tree.key(), leaf_index);
+    receipt.bump = ctx.bumps.receipt;
+    receipt.referrer = referrer;
 
     // Emit bid event
-    emit!(BidPlaced {
+    events.emit(BidPlaced {
+        version: EVENT_VERSION,
         auction: auction.key(),
         bidder: ctx.accounts.bidder.key(),
         amount,
         new_supply: auction.current_supply,
-    });
+        referrer,
+        referral_fee,
+        price: receipt.price,
+        item_serial: receipt.serial,
+        leaf_index,
+        asset_id: receipt.asset_id,
+        payer: ctx.accounts.payer.key(),
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+    if leaf_owner.key() != receipt.bidder {
+        events.emit(ItemGifted {
+            version: EVENT_VERSION,
+            auction: auction.key(),
+            bidder: receipt.bidder,
+            recipient: leaf_owner.key(),
+            item_serial: receipt.serial,
+            asset_id: receipt.asset_id,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        })?;
+    }
+    events.emit_status_change(auction, previous_status, &clock)?;
+
+    Ok(())
+}
+
+/// Index of the next leaf Bubblegum mints into the tree
+fn next_leaf_index(tree_config: &AccountInfo) -> Result<u64> {
+    let tree_config = TreeConfig::from_bytes(&tree_config.try_borrow_data()?)
+        .map_err(|_| SuperpullProgramError::InvalidAccountData)?;
+    Ok(tree_config.num_minted)
+}
+
+/// Checks the bidder's allowlist proof and that `used` is within its allocation
+fn verify_allowlist(
+    bidder: &Pubkey,
+    merkle_root: [u8; 32],
+    allowlist_proof: Option<&AllowlistProof>,
+    used: u64,
+) -> Result<()> {
+    let proof = allowlist_proof.ok_or(SuperpullProgramError::AllowlistProofRequired)?;
+    let leaf = allowlist_leaf(bidder, proof.allocation);
+    require!(
+        verify_proof(&proof.proof, merkle_root, leaf),
+        SuperpullProgramError::InvalidAllowlistProof
+    );
+    require!(
+        used < proof.allocation,
+        SuperpullProgramError::AllowlistAllocationExceeded
+    );
+    Ok(())
+}
+
+/// Checks the bidder's holdings against the auction's token gate.
+///
+/// Remaining accounts:
+/// - `TokenBalance`: the bidder's token account for the gate mint
+/// - `Collection`: the bidder's token account holding the NFT, the NFT's
+///   metadata account and, for one-use gates, the gate marker PDA
+pub fn check_gate<'info>(
+    gate: GateRule,
+    auction: &Pubkey,
+    bidder: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    match gate {
+        GateRule::TokenBalance { mint, min_amount } => {
+            let token_account = remaining_accounts
+                .first()
+                .ok_or(SuperpullProgramError::GateAccountsMissing)?;
+            let holding = read_bidder_token_account(token_account, bidder)?;
+            require!(
+                holding.mint == mint && holding.amount >= min_amount,
+                SuperpullProgramError::GateCheckFailed
+            );
+        }
+        GateRule::Collection { collection_mint, one_use_per_nft } => {
+            let [token_account, metadata, rest @ ..] = remaining_accounts else {
+                return err!(SuperpullProgramError::GateAccountsMissing);
+            };
+            let holding = read_bidder_token_account(token_account, bidder)?;
+            require!(holding.amount >= 1, SuperpullProgramError::GateCheckFailed);
+
+            // Metadata must be the canonical metadata PDA of the held NFT
+            let (metadata_address, _) = Pubkey::find_program_address(
+                &[
+                    b"metadata",
+                    anchor_spl::metadata::ID.as_ref(),
+                    holding.mint.as_ref(),
+                ],
+                &anchor_spl::metadata::ID,
+            );
+            require!(
+                metadata.key() == metadata_address && *metadata.owner == anchor_spl::metadata::ID,
+                SuperpullProgramError::GateCheckFailed
+            );
+            let nft_metadata = MetadataAccount::try_deserialize(&mut &metadata.data.borrow()[..])?;
+            require!(
+                matches!(
+                    nft_metadata.collection,
+                    Some(ref collection) if collection.verified && collection.key == collection_mint
+                ),
+                SuperpullProgramError::GateCheckFailed
+            );
+
+            if one_use_per_nft {
+                let marker = rest
+                    .first()
+                    .ok_or(SuperpullProgramError::GateAccountsMissing)?;
+                create_gate_marker(marker, auction, &holding.mint, bidder, payer, system_program)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Deserializes an SPL token account and checks it belongs to the bidder
+fn read_bidder_token_account(
+    account: &AccountInfo,
+    bidder: &Pubkey,
+) -> Result<token::TokenAccount> {
+    require!(
+        *account.owner == token::ID,
+        SuperpullProgramError::InvalidAccountOwner
+    );
+    let token_account = token::TokenAccount::try_deserialize(&mut &account.data.borrow()[..])?;
+    require!(
+        token_account.owner == *bidder,
+        SuperpullProgramError::GateCheckFailed
+    );
+    Ok(token_account)
+}
+
+/// Creates the marker PDA that records a gating NFT as used
+fn create_gate_marker<'info>(
+    marker: &AccountInfo<'info>,
+    auction: &Pubkey,
+    nft_mint: &Pubkey,
+    bidder: &Pubkey,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let (marker_address, bump) = Pubkey::find_program_address(
+        &[b"gate_marker", auction.as_ref(), nft_mint.as_ref()],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        marker.key(),
+        marker_address,
+        SuperpullProgramError::GateCheckFailed
+    );
+    require!(
+        *marker.owner == system_program::ID && marker.data_is_empty(),
+        SuperpullProgramError::GateNftAlreadyUsed
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"gate_marker",
+        auction.as_ref(),
+        nft_mint.as_ref(),
+        &[bump],
+    ]];
+
+    // Fund the marker, the address may already hold lamports
+    let required_lamports = Rent::get()?.minimum_balance(GateMarker::LEN);
+    let shortfall = required_lamports.saturating_sub(marker.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: marker.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Allocate {
+                account_to_allocate: marker.clone(),
+            },
+            signer_seeds,
+        ),
+        GateMarker::LEN as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::Assign {
+                account_to_assign: marker.clone(),
+            },
+            signer_seeds,
+        ),
+        &crate::ID,
+    )?;
+
+    let gate_marker = GateMarker {
+        auction: *auction,
+        nft_mint: *nft_mint,
+        bidder: *bidder,
+        bump,
+    };
+    gate_marker.try_serialize(&mut &mut marker.data.borrow_mut()[..])?;
 
     Ok(())
-} 
\ No newline at end of file
+}
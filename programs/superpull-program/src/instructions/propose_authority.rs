@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::AuctionState,
+    utils::{errors::SuperpullProgramError, events::{AuthorityProposed, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// The current authority of the auction
+    pub authority: Signer<'info>,
+}
+
+/// Proposes `new_authority` as the next authority, `None` cancels a pending
+/// proposal
+pub fn propose_authority_handler(
+    ctx: Context<ProposeAuthority>,
+    new_authority: Option<Pubkey>,
+) -> Result<()> {
+    // Multisig auctions nominate authorities through approved proposals
+    require!(
+        !ctx.accounts.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_authority_proposal(&mut ctx.accounts.auction, new_authority, &events)
+}
+
+/// Records the nomination, shared by `propose_authority` and approved
+/// multisig proposals
+pub fn apply_authority_proposal(
+    auction: &mut Account<AuctionState>,
+    new_authority: Option<Pubkey>,
+    events: &EventEmitter,
+) -> Result<()> {
+    // Validate new authority
+    if let Some(new_authority) = new_authority {
+        require!(
+            !new_authority.eq(&Pubkey::default()),
+            SuperpullProgramError::InvalidAuthority
+        );
+    }
+
+    auction.pending_authority = new_authority;
+
+    let clock = Clock::get()?;
+    events.emit(AuthorityProposed {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        authority: auction.authority,
+        pending_authority: new_authority,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
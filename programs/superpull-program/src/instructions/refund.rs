@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token;
+use anchor_spl::token::Token;
 use crate::{
-    state::{AuctionState, BidState},
-    utils::{errors::SuperpullProgramError, events::BidRefunded},
+    instructions::transfer_from_auction,
+    state::{AuctionState, BidState, ItemReceipt},
+    utils::{errors::SuperpullProgramError, events::{BidRefunded, EventEmitter, EVENT_VERSION}},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Refund<'info> {
     #[account(mut)]
@@ -36,68 +38,130 @@ pub struct Refund<'info> {
     #[account(mut)]
     pub auction_token_account: AccountInfo<'info>,
 
-    /// CHECK: Validated by token program
-    pub token_program: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
 
     /// CHECK: Validated by system program
     pub system_program: AccountInfo<'info>,
 }
 
-pub fn refund_handler(ctx: Context<Refund>) -> Result<()> {
-    let auction = &ctx.accounts.auction;
-    let bid = &ctx.accounts.bid;
-    
-    // Check if auction has expired and not graduated
-    let current_time = Clock::get()?.unix_timestamp;
-    require!(
-        current_time > auction.deadline && !auction.is_graduated,
-        SuperpullProgramError::InvalidRefundAttempt
-    );
+/// Refunds the bidder's bids in a failed or halted auction. Items refunded
+/// this way keep their cNFT, `refund_item` refunds a single item and burns it.
+///
+/// Remaining accounts: every open item receipt of the bid, closed to the
+/// bidder
+pub fn refund_handler<'info>(ctx: Context<'_, '_, 'info, 'info, Refund<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    require_refundable(&ctx.accounts.auction, clock.unix_timestamp)?;
 
     // Check if there's anything to refund
+    let bid = &ctx.accounts.bid;
     require!(bid.amount > 0, SuperpullProgramError::NoFundsToRefund);
 
-    msg!("TODO: Implement NFT burning");
+    let refund_amount = release_refund(&mut ctx.accounts.auction, bid.amount, bid.referral_fees)?;
 
     // Transfer tokens from auction account back to bidder
-    let seeds = &[
-        b"auction",
-        auction.authority.as_ref(),
-        auction.collection_mint.as_ref(),
-        &[auction.bump],
-    ];
-    let signer = &[&seeds[..]];
-
-    let cpi_accounts = token::Transfer {
-        from: ctx.accounts.auction_token_account.to_account_info(),
-        to: ctx.accounts.bidder_token_account.to_account_info(),
-        authority: ctx.accounts.auction.to_account_info(),
-    };
-
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer,
-    );
-    token::transfer(cpi_ctx, bid.amount)?;
-
-    // Update auction state
-    let auction = &mut ctx.accounts.auction;
-    auction.total_value_locked = auction.total_value_locked
-        .checked_sub(bid.amount)
-        .ok_or(SuperpullProgramError::MathOverflow)?;
+    let auction = &ctx.accounts.auction;
+    transfer_from_auction(
+        auction,
+        &ctx.accounts.auction_token_account,
+        &ctx.accounts.bidder_token_account,
+        &ctx.accounts.token_program.to_account_info(),
+        refund_amount,
+    )?;
 
     // Update bid state
     let bid = &mut ctx.accounts.bid;
-    let refunded_amount = bid.amount;
     bid.amount = 0;
+    bid.referral_fees = 0;
+
+    close_receipts(
+        &auction.key(),
+        bid,
+        &ctx.accounts.bidder.to_account_info(),
+        ctx.remaining_accounts,
+    )?;
 
     // Emit refund event
-    emit!(BidRefunded {
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(BidRefunded {
+        version: EVENT_VERSION,
         auction: auction.key(),
         bidder: ctx.accounts.bidder.key(),
-        amount: refunded_amount,
-    });
+        amount: refund_amount,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
+
+/// Checks that bids of the auction can be refunded: it expired without
+/// graduating, the seller missed the delivery deadline, or it was halted by
+/// its backers
+pub fn require_refundable(auction: &AuctionState, now: i64) -> Result<()> {
+    require!(
+        (now > auction.deadline && !auction.is_graduated)
+            || auction.is_delivery_failed(now)
+            || auction.is_halted,
+        SuperpullProgramError::InvalidRefundAttempt
+    );
+    Ok(())
+}
+
+/// Releases `amount` of bids, `referral_fees` of which were credited to
+/// referrers, from the auction and returns the amount to refund.
+///
+/// A failed auction or missed delivery refunds the full amount, a halted
+/// auction the share of the remaining escrow
+pub fn release_refund(auction: &mut AuctionState, amount: u64, referral_fees: u64) -> Result<u64> {
+    let net_amount = amount
+        .checked_sub(referral_fees)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    // Referral fees were never part of the TVL and stay owed to the
+    // referrers of a halted auction
+    if auction.is_halted {
+        let refund_amount = auction.halted_refund_amount(net_amount)?;
+        auction.total_value_locked = auction.total_value_locked
+            .checked_sub(refund_amount)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        Ok(refund_amount)
+    } else {
+        auction.total_value_locked = auction.total_value_locked
+            .checked_sub(net_amount)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        auction.referral_fees_owed = auction.referral_fees_owed
+            .checked_sub(referral_fees)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        Ok(amount)
+    }
+}
+
+/// Closes every open item receipt of `bid` in `auction`, sending their rent
+/// to the bidder
+pub fn close_receipts<'info>(
+    auction: &Pubkey,
+    bid: &mut BidState,
+    bidder: &AccountInfo<'info>,
+    receipts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    // A receipt left open could still be refunded through `refund_item`
+    require!(
+        receipts.len() as u64 == bid.open_receipts,
+        SuperpullProgramError::OpenItemReceipts
+    );
+
+    // A closed receipt is no longer owned by the program, so the same
+    // receipt cannot be counted twice
+    for receipt in receipts {
+        let receipt = Account::<ItemReceipt>::try_from(receipt)?;
+        require!(
+            receipt.auction == *auction && receipt.bidder == bid.bidder,
+            SuperpullProgramError::InvalidAccountData
+        );
+        receipt.close(bidder.clone())?;
+    }
+    bid.open_receipts = 0;
 
     Ok(())
 }
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address, token::{Token, TokenAccount}};
+use crate::{
+    instructions::{close_receipts, release_refund, require_refundable, transfer_from_auction},
+    state::{AuctionState, BidState, MAX_REFUND_BOUNTY_LAMPORTS},
+    utils::{errors::SuperpullProgramError, events::{BidRefunded, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundBatch<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    /// Anyone can crank refunds, for an optional bounty taken from the rent
+    /// of the closed bid accounts
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// The auction's token account to refund from
+    /// CHECK: Validated through token program CPI
+    #[account(mut)]
+    pub auction_token_account: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Refunds bidders of a failed or halted auction without their signature
+/// and closes their bid accounts and item receipts. The rent of each closed
+/// account goes back to its bidder, except for up to `bounty` lamports of
+/// each bid paid to the cranker.
+///
+/// Remaining accounts: groups of a bid account, its open item receipts, the
+/// bidder's associated token account for the auction's mint and the
+/// bidder's wallet. Bids without a usable associated token account are
+/// skipped
+pub fn refund_batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>,
+    bounty: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require_refundable(&ctx.accounts.auction, clock.unix_timestamp)?;
+    require!(
+        bounty <= MAX_REFUND_BOUNTY_LAMPORTS,
+        SuperpullProgramError::InvalidRefundBounty
+    );
+    require!(
+        !ctx.remaining_accounts.is_empty(),
+        SuperpullProgramError::InvalidRefundAccounts
+    );
+
+    let auction_key = ctx.accounts.auction.key();
+    let token_mint = ctx.accounts.auction.token_mint;
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    let mut remaining = ctx.remaining_accounts;
+    while let Some((bid, rest)) = remaining.split_first() {
+        // A closed bid is no longer owned by the program, so the same bid
+        // cannot be refunded twice
+        let mut bid = Account::<BidState>::try_from(bid)?;
+        let receipt_count = usize::try_from(bid.open_receipts)
+            .map_err(|_| SuperpullProgramError::InvalidRefundAccounts)?;
+        require!(
+            rest.len() >= receipt_count + 2,
+            SuperpullProgramError::InvalidRefundAccounts
+        );
+        let (receipts, rest) = rest.split_at(receipt_count);
+        let (bidder_token_account, bidder) = (&rest[0], &rest[1]);
+        remaining = &rest[2..];
+
+        check_refund_bid(&auction_key, &bid, &bidder.key())?;
+
+        if bid.amount > 0 {
+            // A missing token account would fail the whole batch, the
+            // bidder can still refund on their own
+            if !is_refund_token_account(&token_mint, &bid.bidder, bidder_token_account) {
+                continue;
+            }
+
+            let refund_amount = release_refund(&mut ctx.accounts.auction, bid.amount, bid.referral_fees)?;
+            if refund_amount > 0 {
+                transfer_from_auction(
+                    &ctx.accounts.auction,
+                    &ctx.accounts.auction_token_account,
+                    bidder_token_account,
+                    &token_program,
+                    refund_amount,
+                )?;
+            }
+
+            events.emit(BidRefunded {
+                version: EVENT_VERSION,
+                auction: auction_key,
+                bidder: bid.bidder,
+                amount: refund_amount,
+                timestamp: clock.unix_timestamp,
+                slot: clock.slot,
+            })?;
+        }
+
+        close_receipts(&auction_key, &mut bid, bidder, receipts)?;
+
+        // Pay the bounty out of the bid's rent, the rest goes to the bidder
+        let cranker_share = bounty.min(bid.to_account_info().lamports());
+        bid.sub_lamports(cranker_share)?;
+        ctx.accounts.cranker.add_lamports(cranker_share)?;
+        bid.close(bidder.clone())?;
+    }
+
+    Ok(())
+}
+
+/// Checks a bid passed to `refund_batch` belongs to the auction and is
+/// paired with the bidder's wallet
+pub fn check_refund_bid(auction: &Pubkey, bid: &BidState, bidder: &Pubkey) -> Result<()> {
+    require_keys_eq!(bid.auction, *auction, SuperpullProgramError::InvalidRefundAccounts);
+    require_keys_eq!(bid.bidder, *bidder, SuperpullProgramError::InvalidRefundAccounts);
+    Ok(())
+}
+
+/// Whether `token_account` is the bidder's initialized associated token
+/// account for the auction's mint, the only account `refund_batch` pays to
+pub fn is_refund_token_account(token_mint: &Pubkey, bidder: &Pubkey, token_account: &AccountInfo) -> bool {
+    token_account.key() == get_associated_token_address(bidder, token_mint)
+        && *token_account.owner == anchor_spl::token::ID
+        && TokenAccount::try_deserialize(&mut &token_account.data.borrow()[..])
+            .is_ok_and(|account| account.mint == *token_mint && account.owner == *bidder)
+}
@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token};
+use mpl_bubblegum::instructions::{BurnCpi, BurnCpiAccounts, BurnInstructionArgs};
+use crate::{
+    instructions::{release_refund, require_refundable, transfer_from_auction},
+    state::{AuctionState, BidState, ItemReceipt, ReferrerState},
+    utils::{errors::SuperpullProgramError, events::{EventEmitter, ItemRefunded, EVENT_VERSION}},
+};
+
+/// Leaf of the refunded item's cNFT, as returned by the DAS API
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ItemLeaf {
+    /// Current root of the merkle tree
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundItem<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction.key().as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump = bid.bump,
+        has_one = auction,
+        has_one = bidder,
+    )]
+    pub bid: Account<'info, BidState>,
+
+    /// Receipt of the refunded item, closed to the bidder
+    #[account(
+        mut,
+        seeds = [
+            b"receipt",
+            auction.key().as_ref(),
+            receipt.serial.to_le_bytes().as_ref(),
+        ],
+        bump = receipt.bump,
+        has_one = auction,
+        has_one = bidder,
+        close = bidder,
+    )]
+    pub receipt: Account<'info, ItemReceipt>,
+
+    /// Referrer of the item, required when the receipt has one
+    #[account(
+        mut,
+        seeds = [
+            b"referrer",
+            auction.key().as_ref(),
+            referrer_state.referrer.as_ref(),
+        ],
+        bump = referrer_state.bump,
+        constraint = referrer_state.auction == auction.key() @ SuperpullProgramError::InvalidAccountData,
+    )]
+    pub referrer_state: Option<Account<'info, ReferrerState>>,
+
+    /// The bidder who paid for the item, receives the refund and the
+    /// receipt's rent
+    /// CHECK: Matched against the bid and the receipt
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+
+    /// Owner of the item's cNFT, the bidder or the recipient of a gift
+    pub owner: Signer<'info>,
+
+    /// The bidder's token account to receive refund
+    /// CHECK: Validated through token program CPI and constraint
+    #[account(
+        mut,
+        constraint = *bidder_token_account.owner == token_program.key(),
+        constraint = token::TokenAccount::try_deserialize(&mut &bidder_token_account.data.borrow()[..])
+            .is_ok_and(|token_account| {
+                token_account.mint == auction.token_mint && token_account.owner == bidder.key()
+            }) @ SuperpullProgramError::InvalidTokenAccount
+    )]
+    pub bidder_token_account: AccountInfo<'info>,
+
+    /// The auction's token account to refund from
+    /// CHECK: Validated through token program CPI
+    #[account(mut)]
+    pub auction_token_account: AccountInfo<'info>,
+
+    /// CHECK: Validated by Bubblegum program
+    #[account(mut, address = auction.merkle_tree)]
+    pub merkle_tree: AccountInfo<'info>,
+    /// CHECK: Validated by Bubblegum program
+    pub tree_config: AccountInfo<'info>,
+    /// CHECK: Validated by Log Wrapper program
+    pub log_wrapper: AccountInfo<'info>,
+    /// CHECK: Validated by Compression program
+    pub compression_program: AccountInfo<'info>,
+    /// CHECK: The burn must go through Bubblegum for the refund to be paid
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Refunds a single item at the price recorded in its receipt and burns
+/// its cNFT. The owner of the cNFT signs, the refund goes to the bidder who
+/// paid for it.
+///
+/// Remaining accounts: the merkle proof of the item's leaf
+pub fn refund_item_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RefundItem<'info>>,
+    leaf: ItemLeaf,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require_refundable(&ctx.accounts.auction, clock.unix_timestamp)?;
+
+    let receipt = &ctx.accounts.receipt;
+    require!(
+        ctx.accounts.bid.amount >= receipt.price,
+        SuperpullProgramError::NoFundsToRefund
+    );
+    require!(
+        ctx.accounts.referrer_state.as_ref().map(|state| state.referrer) == receipt.referrer,
+        SuperpullProgramError::ReferrerMismatch
+    );
+
+    // Burn the item, only its current owner can refund it
+    let owner = ctx.accounts.owner.to_account_info();
+    burn_item(
+        &ctx.accounts.bubblegum_program,
+        BurnCpiAccounts {
+            tree_config: &ctx.accounts.tree_config,
+            leaf_owner: (&owner, true),
+            leaf_delegate: (&owner, false),
+            merkle_tree: &ctx.accounts.merkle_tree,
+            log_wrapper: &ctx.accounts.log_wrapper,
+            compression_program: &ctx.accounts.compression_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        receipt,
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+
+    let refund_amount = release_refund(&mut ctx.accounts.auction, receipt.price, receipt.referral_fee)?;
+
+    // Fees stay owed to the referrers of a halted auction, otherwise the
+    // item no longer counts towards its referrer's totals
+    if !ctx.accounts.auction.is_halted {
+        if let Some(referrer_state) = ctx.accounts.referrer_state.as_mut() {
+            referrer_state.release_item(receipt.price, receipt.referral_fee)?;
+        }
+    }
+
+    // Transfer the item's price from auction account back to bidder
+    let auction = &ctx.accounts.auction;
+    transfer_from_auction(
+        auction,
+        &ctx.accounts.auction_token_account,
+        &ctx.accounts.bidder_token_account,
+        &ctx.accounts.token_program.to_account_info(),
+        refund_amount,
+    )?;
+
+    // Update bid state
+    let bid = &mut ctx.accounts.bid;
+    bid.amount = bid.amount
+        .checked_sub(receipt.price)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.referral_fees = bid.referral_fees
+        .checked_sub(receipt.referral_fee)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.count = bid.count
+        .checked_sub(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.open_receipts = bid.open_receipts
+        .checked_sub(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ItemRefunded {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        bidder: ctx.accounts.bidder.key(),
+        item_serial: receipt.serial,
+        asset_id: receipt.asset_id,
+        amount: refund_amount,
+        price: receipt.price,
+        referral_fee: receipt.referral_fee,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
+
+/// Burns the cNFT of the item recorded in `receipt`, `proof` is the merkle
+/// proof of its leaf
+pub fn burn_item<'a, 'b>(
+    bubblegum_program: &'b AccountInfo<'a>,
+    accounts: BurnCpiAccounts<'a, 'b>,
+    receipt: &ItemReceipt,
+    leaf: &ItemLeaf,
+    proof: &'b [AccountInfo<'a>],
+) -> Result<()> {
+    let proof: Vec<(&AccountInfo<'a>, bool, bool)> = proof
+        .iter()
+        .map(|node| (node, false, false))
+        .collect();
+    BurnCpi::new(
+        bubblegum_program,
+        accounts,
+        BurnInstructionArgs {
+            root: leaf.root,
+            data_hash: leaf.data_hash,
+            creator_hash: leaf.creator_hash,
+            nonce: receipt.leaf_index,
+            index: u32::try_from(receipt.leaf_index)
+                .map_err(|_| SuperpullProgramError::MathOverflow)?,
+        },
+    )
+    .invoke_with_remaining_accounts(&proof)?;
+    Ok(())
+}
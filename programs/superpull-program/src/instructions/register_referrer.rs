@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionState, ReferrerState},
+    utils::{errors::SuperpullProgramError, events::{ReferrerRegistered, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = ReferrerState::LEN,
+        seeds = [
+            b"referrer",
+            auction.key().as_ref(),
+            referrer.key().as_ref(),
+        ],
+        bump
+    )]
+    pub referrer_state: Account<'info, ReferrerState>,
+
+    /// The promoter who will be credited for referred bids
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referrer_handler(ctx: Context<RegisterReferrer>) -> Result<()> {
+    require!(
+        !ctx.accounts.referrer.key().eq(&Pubkey::default()),
+        SuperpullProgramError::InvalidAuthority
+    );
+
+    let referrer_state = &mut ctx.accounts.referrer_state;
+    referrer_state.auction = ctx.accounts.auction.key();
+    referrer_state.referrer = ctx.accounts.referrer.key();
+    referrer_state.referral_count = 0;
+    referrer_state.total_volume = 0;
+    referrer_state.total_rewards = 0;
+    referrer_state.claimed_rewards = 0;
+    referrer_state.bump = ctx.bumps.referrer_state;
+
+    let clock = Clock::get()?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ReferrerRegistered {
+        version: EVENT_VERSION,
+        auction: referrer_state.auction,
+        referrer: referrer_state.referrer,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use mpl_bubblegum::instructions::BurnCpiAccounts;
+use crate::{
+    instructions::{burn_item, transfer_from_auction, ItemLeaf},
+    state::{fee_amount, AuctionState, BidState, ItemReceipt, ReferrerState},
+    utils::{errors::SuperpullProgramError, events::{EventEmitter, ItemSoldBack, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SellBack<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction.key().as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump = bid.bump,
+        has_one = auction,
+        has_one = bidder,
+    )]
+    pub bid: Account<'info, BidState>,
+
+    /// Receipt of the item sold back, closed to the bidder
+    #[account(
+        mut,
+        seeds = [
+            b"receipt",
+            auction.key().as_ref(),
+            receipt.serial.to_le_bytes().as_ref(),
+        ],
+        bump = receipt.bump,
+        has_one = auction,
+        has_one = bidder,
+        close = bidder,
+    )]
+    pub receipt: Account<'info, ItemReceipt>,
+
+    /// Referrer of the item, required when the receipt has one
+    #[account(
+        mut,
+        seeds = [
+            b"referrer",
+            auction.key().as_ref(),
+            referrer_state.referrer.as_ref(),
+        ],
+        bump = referrer_state.bump,
+        constraint = referrer_state.auction == auction.key() @ SuperpullProgramError::InvalidAccountData,
+    )]
+    pub referrer_state: Option<Account<'info, ReferrerState>>,
+
+    /// Owner of the item's cNFT
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// The bidder's token account to receive the proceeds
+    #[account(
+        mut,
+        token::mint = auction.token_mint,
+        token::authority = bidder,
+    )]
+    pub bidder_token_account: Account<'info, TokenAccount>,
+
+    /// The auction's token account to pay from
+    /// CHECK: Validated through token program CPI
+    #[account(mut)]
+    pub auction_token_account: AccountInfo<'info>,
+
+    /// CHECK: Validated by Bubblegum program
+    #[account(mut, address = auction.merkle_tree)]
+    pub merkle_tree: AccountInfo<'info>,
+    /// CHECK: Validated by Bubblegum program
+    pub tree_config: AccountInfo<'info>,
+    /// CHECK: Validated by Log Wrapper program
+    pub log_wrapper: AccountInfo<'info>,
+    /// CHECK: Validated by Compression program
+    pub compression_program: AccountInfo<'info>,
+    /// CHECK: The burn must go through Bubblegum for the item to be bought back
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sells an item back to the curve before graduation and burns its cNFT.
+///
+/// Remaining accounts: the merkle proof of the item's leaf
+pub fn sell_back_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SellBack<'info>>,
+    leaf: ItemLeaf,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let auction = &ctx.accounts.auction;
+
+    let spread_bps = auction.sell_back_spread_bps
+        .ok_or(SuperpullProgramError::SellBackDisabled)?;
+
+    // Phase sales have their own supply and pricing
+    require!(!auction.has_phases, SuperpullProgramError::SellBackDisabled);
+    require!(
+        !auction.is_graduated && clock.unix_timestamp <= auction.deadline,
+        SuperpullProgramError::SellBackClosed
+    );
+    require!(!auction.is_halted, SuperpullProgramError::AuctionHalted);
+    let previous_status = auction.registry_status(clock.unix_timestamp);
+
+    let receipt = &ctx.accounts.receipt;
+    require!(
+        ctx.accounts.bid.amount >= receipt.price,
+        SuperpullProgramError::NoFundsToRefund
+    );
+    require!(
+        ctx.accounts.referrer_state.as_ref().map(|state| state.referrer) == receipt.referrer,
+        SuperpullProgramError::ReferrerMismatch
+    );
+
+    // Burn the item, only its current owner can sell it back
+    let bidder = ctx.accounts.bidder.to_account_info();
+    burn_item(
+        &ctx.accounts.bubblegum_program,
+        BurnCpiAccounts {
+            tree_config: &ctx.accounts.tree_config,
+            leaf_owner: (&bidder, true),
+            leaf_delegate: (&bidder, false),
+            merkle_tree: &ctx.accounts.merkle_tree,
+            log_wrapper: &ctx.accounts.log_wrapper,
+            compression_program: &ctx.accounts.compression_program,
+            system_program: &ctx.accounts.system_program,
+        },
+        receipt,
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+
+    // Release the item from the escrow, its referral fee is no longer owed
+    let split = release_sell_back(
+        &mut ctx.accounts.auction,
+        receipt.price,
+        receipt.referral_fee,
+        spread_bps,
+    )?;
+    if let Some(referrer_state) = ctx.accounts.referrer_state.as_mut() {
+        referrer_state.release_item(receipt.price, receipt.referral_fee)?;
+    }
+
+    // Pay the bidder from the escrow, the rest of the price stays locked
+    let auction = &ctx.accounts.auction;
+    if split.amount > 0 {
+        transfer_from_auction(
+            auction,
+            &ctx.accounts.auction_token_account,
+            &ctx.accounts.bidder_token_account.to_account_info(),
+            &ctx.accounts.token_program.to_account_info(),
+            split.amount,
+        )?;
+    }
+
+    // Update bid state
+    let bid = &mut ctx.accounts.bid;
+    bid.amount = bid.amount
+        .checked_sub(receipt.price)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.referral_fees = bid.referral_fees
+        .checked_sub(receipt.referral_fee)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.count = bid.count
+        .checked_sub(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    bid.open_receipts = bid.open_receipts
+        .checked_sub(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ItemSoldBack {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        bidder: bidder.key(),
+        item_serial: receipt.serial,
+        asset_id: receipt.asset_id,
+        price: receipt.price,
+        referral_fee: receipt.referral_fee,
+        sell_price: split.sell_price,
+        spread: split.spread,
+        amount: split.amount,
+        new_supply: auction.current_supply,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+    events.emit_status_change(auction, previous_status, &clock)?;
+
+    Ok(())
+}
+
+/// Split of a sold back item's price
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SellBackSplit {
+    /// Price the item is sold back at
+    pub sell_price: u64,
+    /// Seller's spread, taken from the sell price
+    pub spread: u64,
+    /// Paid to the bidder: the sell price minus the spread
+    pub amount: u64,
+    /// Kept in the TVL until withdrawn: the spread and the part of the
+    /// item's price above the sell price
+    pub retained: u64,
+}
+
+/// Releases an item of `price`, `referral_fee` of which was credited to its
+/// referrer, from the auction and splits its price between the bidder and
+/// the TVL.
+///
+/// Only the bidder's amount leaves the escrow. The referral fee is no longer
+/// owed, so it joins the rest of the price in the TVL, where the seller can
+/// only get it through `withdraw` once the auction graduates
+pub fn release_sell_back(
+    auction: &mut AuctionState,
+    price: u64,
+    referral_fee: u64,
+    spread_bps: u16,
+) -> Result<SellBackSplit> {
+    let sell_price = auction.sell_back_price(price)?;
+    let spread = fee_amount(sell_price, spread_bps)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    let amount = sell_price
+        .checked_sub(spread)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    let retained = price
+        .checked_sub(amount)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    auction.current_supply = auction.current_supply
+        .checked_sub(1)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    auction.total_value_locked = auction.total_value_locked
+        .checked_add(referral_fee)
+        .and_then(|total| total.checked_sub(amount))
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    auction.referral_fees_owed = auction.referral_fees_owed
+        .checked_sub(referral_fee)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+
+    Ok(SellBackSplit {
+        sell_price,
+        spread,
+        amount,
+        retained,
+    })
+}
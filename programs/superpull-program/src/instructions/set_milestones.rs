@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionMilestones, AuctionState, Milestone, BPS_DENOMINATOR, MAX_MILESTONES},
+    utils::{errors::SuperpullProgramError, events::{MilestonesConfigured, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetMilestones<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AuctionMilestones::LEN,
+        seeds = [
+            b"milestones",
+            auction.key().as_ref(),
+        ],
+        bump
+    )]
+    pub milestones: Account<'info, AuctionMilestones>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+
+    /// The account that will pay for the milestones account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_milestones_handler(
+    ctx: Context<SetMilestones>,
+    release_bps: Vec<u16>,
+    challenge_period: i64,
+    veto_threshold_bps: u16,
+) -> Result<()> {
+    // Multisig auctions set milestones through approved proposals
+    require!(
+        !ctx.accounts.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_milestones(
+        &mut ctx.accounts.auction,
+        &mut ctx.accounts.milestones,
+        ctx.bumps.milestones,
+        release_bps,
+        challenge_period,
+        veto_threshold_bps,
+        &events,
+    )
+}
+
+/// Validates and stores the milestones, shared by `set_milestones` and
+/// approved multisig proposals
+pub fn apply_milestones(
+    auction: &mut Account<AuctionState>,
+    auction_milestones: &mut Account<AuctionMilestones>,
+    bump: u8,
+    release_bps: Vec<u16>,
+    challenge_period: i64,
+    veto_threshold_bps: u16,
+    events: &EventEmitter,
+) -> Result<()> {
+    // Backers must know the milestones before they bid
+    require!(
+        auction.current_supply == 0,
+        SuperpullProgramError::MilestonesLocked
+    );
+
+    // Milestones replace the vesting schedule
+    require!(
+        auction.vesting.is_none(),
+        SuperpullProgramError::InvalidMilestoneConfig
+    );
+
+    // Validate milestone count and shares
+    require!(
+        !release_bps.is_empty() && release_bps.len() <= MAX_MILESTONES,
+        SuperpullProgramError::InvalidMilestoneConfig
+    );
+    require!(
+        release_bps.iter().all(|bps| *bps > 0),
+        SuperpullProgramError::InvalidMilestoneConfig
+    );
+    let total_bps: u64 = release_bps.iter().map(|bps| *bps as u64).sum();
+    require!(
+        total_bps == BPS_DENOMINATOR,
+        SuperpullProgramError::InvalidMilestoneConfig
+    );
+
+    // Validate challenge window and veto threshold
+    require!(
+        challenge_period > 0,
+        SuperpullProgramError::InvalidMilestoneConfig
+    );
+    require!(
+        veto_threshold_bps > 0 && veto_threshold_bps as u64 <= BPS_DENOMINATOR,
+        SuperpullProgramError::InvalidMilestoneConfig
+    );
+
+    // Store milestones
+    auction_milestones.auction = auction.key();
+    auction_milestones.challenge_period = challenge_period;
+    auction_milestones.veto_threshold_bps = veto_threshold_bps;
+    auction_milestones.milestones = release_bps
+        .into_iter()
+        .map(|release_bps| Milestone {
+            release_bps,
+            ..Default::default()
+        })
+        .collect();
+    auction_milestones.bump = bump;
+
+    auction.has_milestones = true;
+
+    let clock = Clock::get()?;
+    events.emit(MilestonesConfigured {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        milestone_count: auction_milestones.milestones.len() as u8,
+        challenge_period,
+        veto_threshold_bps,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
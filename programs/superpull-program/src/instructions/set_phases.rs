@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionPhases, AuctionState, Phase, PhaseArgs, MAX_PHASES},
+    utils::{errors::SuperpullProgramError, events::{PhasesConfigured, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetPhases<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = AuctionPhases::LEN,
+        seeds = [
+            b"phases",
+            auction.key().as_ref(),
+        ],
+        bump
+    )]
+    pub phases: Account<'info, AuctionPhases>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+
+    /// The account that will pay for the phases account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_phases_handler(ctx: Context<SetPhases>, phases: Vec<PhaseArgs>) -> Result<()> {
+    // Multisig auctions set phases through approved proposals
+    require!(
+        !ctx.accounts.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_phases(
+        &mut ctx.accounts.auction,
+        &mut ctx.accounts.phases,
+        ctx.bumps.phases,
+        phases,
+        &events,
+    )
+}
+
+/// Validates and stores the phases, shared by `set_phases` and approved
+/// multisig proposals
+pub fn apply_phases(
+    auction: &mut Account<AuctionState>,
+    auction_phases: &mut Account<AuctionPhases>,
+    bump: u8,
+    phases: Vec<PhaseArgs>,
+    events: &EventEmitter,
+) -> Result<()> {
+    // Phases are fixed once the first item is sold
+    require!(
+        auction.current_supply == 0,
+        SuperpullProgramError::PhasesLocked
+    );
+
+    // Phases replace the single allowlist presale
+    require!(
+        auction.allowlist.is_none(),
+        SuperpullProgramError::InvalidPhaseConfig
+    );
+
+    // Validate phase count
+    require!(
+        !phases.is_empty() && phases.len() <= MAX_PHASES,
+        SuperpullProgramError::InvalidPhaseConfig
+    );
+
+    // Validate each phase, phases must be sorted and must not overlap
+    let mut total_supply: u64 = 0;
+    let mut previous_end = i64::MIN;
+    for phase in phases.iter() {
+        require!(
+            phase.start_time < phase.end_time && phase.start_time >= previous_end,
+            SuperpullProgramError::InvalidPhaseConfig
+        );
+        require!(
+            phase.start_time < auction.deadline,
+            SuperpullProgramError::InvalidPhaseConfig
+        );
+        require!(phase.base_price > 0, SuperpullProgramError::InvalidBasePrice);
+        require!(phase.supply > 0, SuperpullProgramError::InvalidPhaseConfig);
+        require!(
+            phase.max_per_wallet <= phase.supply,
+            SuperpullProgramError::InvalidMaxPerWallet
+        );
+
+        previous_end = phase.end_time;
+        total_supply = total_supply
+            .checked_add(phase.supply)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+    }
+    require!(
+        total_supply <= auction.max_supply,
+        SuperpullProgramError::InvalidPhaseConfig
+    );
+
+    // Store phases
+    auction_phases.auction = auction.key();
+    auction_phases.phases = phases.into_iter().map(Phase::from).collect();
+    auction_phases.bump = bump;
+
+    auction.has_phases = true;
+
+    let clock = Clock::get()?;
+    events.emit(PhasesConfigured {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        phase_count: auction_phases.phases.len() as u8,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
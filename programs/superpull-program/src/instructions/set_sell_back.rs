@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionState, MAX_SELL_BACK_SPREAD_BPS},
+    utils::{errors::SuperpullProgramError, events::{SellBackConfigured, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetSellBack<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+}
+
+/// Enables sell-backs with the given spread, or disables them when unset
+pub fn set_sell_back_handler(ctx: Context<SetSellBack>, spread_bps: Option<u16>) -> Result<()> {
+    // Multisig auctions configure sell-backs through approved proposals
+    require!(
+        !ctx.accounts.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_sell_back(&mut ctx.accounts.auction, spread_bps, &events)
+}
+
+/// Sets the sell-back spread, shared by `set_sell_back` and approved
+/// multisig proposals
+pub fn apply_sell_back(
+    auction: &mut Account<AuctionState>,
+    spread_bps: Option<u16>,
+    events: &EventEmitter,
+) -> Result<()> {
+    // Bidders buy under the sell-back terms, they are fixed once the first
+    // item is sold
+    require!(
+        auction.current_supply == 0 && auction.last_item_serial == 0,
+        SuperpullProgramError::SellBackLocked
+    );
+
+    // Validate spread
+    require!(
+        spread_bps.unwrap_or_default() <= MAX_SELL_BACK_SPREAD_BPS,
+        SuperpullProgramError::InvalidSellBackSpread
+    );
+
+    auction.sell_back_spread_bps = spread_bps;
+
+    let clock = Clock::get()?;
+    events.emit(SellBackConfigured {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        spread_bps,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::AuctionState,
+    utils::{errors::SuperpullProgramError, events::{TreasuryUpdated, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+}
+
+pub fn set_treasury_handler(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+    // Multisig auctions update the treasury through approved proposals
+    require!(
+        !ctx.accounts.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    // Validate treasury
+    require!(
+        !treasury.eq(&Pubkey::default()),
+        SuperpullProgramError::InvalidTreasury
+    );
+
+    let auction = &mut ctx.accounts.auction;
+    auction.treasury = treasury;
+
+    let clock = Clock::get()?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(TreasuryUpdated {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        treasury,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionMilestones, AuctionState},
+    utils::{errors::SuperpullProgramError, events::{MilestoneSubmitted, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitMilestone<'info> {
+    #[account(
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [b"milestones", auction.key().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Account<'info, AuctionMilestones>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+}
+
+pub fn submit_milestone_handler(
+    ctx: Context<SubmitMilestone>,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+
+    // Validate auction state
+    require!(
+        auction.is_graduated,
+        SuperpullProgramError::NotGraduated
+    );
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+
+    // Milestones are submitted in order, one challenge window at a time
+    let milestones = &mut ctx.accounts.milestones;
+    let index = milestones
+        .next_index()
+        .ok_or(SuperpullProgramError::NoPendingMilestone)?;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    if index > 0 {
+        let previous = milestones.milestones[index - 1];
+        require!(
+            current_time >= milestones.challenge_end(&previous)?,
+            SuperpullProgramError::ChallengeWindowOpen
+        );
+    }
+
+    // Open the challenge window
+    let milestone = &mut milestones.milestones[index];
+    milestone.submitted_at = current_time;
+    milestone.evidence_hash = evidence_hash;
+    let milestone = *milestone;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(MilestoneSubmitted {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        milestone_index: index as u8,
+        release_bps: milestone.release_bps,
+        evidence_hash,
+        challenge_ends_at: milestones.challenge_end(&milestone)?,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
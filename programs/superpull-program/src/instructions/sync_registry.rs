@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{registry_location, AuctionState, Registry, RegistryPage},
+    utils::{errors::SuperpullProgramError, events::{RegistryStatusUpdated, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SyncRegistry<'info> {
+    pub auction: Account<'info, AuctionState>,
+
+    /// Registry of every auction
+    #[account(
+        seeds = [b"registry"],
+        bump = global_registry.bump,
+    )]
+    pub global_registry: Box<Account<'info, Registry>>,
+
+    /// Page of the global registry listing the auction
+    #[account(
+        mut,
+        constraint = global_registry_page.registry == global_registry.key() @ SuperpullProgramError::InvalidRegistryPage,
+    )]
+    pub global_registry_page: Box<Account<'info, RegistryPage>>,
+
+    /// Registry of the auctions of the authority that created the auction
+    #[account(
+        seeds = [b"registry", auction.seed_authority().as_ref()],
+        bump = authority_registry.bump,
+    )]
+    pub authority_registry: Box<Account<'info, Registry>>,
+
+    /// Page of the authority's registry listing the auction
+    #[account(
+        mut,
+        constraint = authority_registry_page.registry == authority_registry.key() @ SuperpullProgramError::InvalidRegistryPage,
+    )]
+    pub authority_registry_page: Box<Account<'info, RegistryPage>>,
+}
+
+/// Refreshes the status flags of the auction's registry entries, callable by
+/// anyone
+pub fn sync_registry_handler(ctx: Context<SyncRegistry>) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let position = auction
+        .registry_position
+        .ok_or(SuperpullProgramError::AuctionNotRegistered)?;
+    let clock = Clock::get()?;
+    let status = auction.registry_status(clock.unix_timestamp);
+
+    // Time-based changes (expiry, missed delivery) surface when synced
+    let previous_status = set_entry_status(
+        &mut ctx.accounts.global_registry_page,
+        position.global_index,
+        auction.key(),
+        status,
+    )?;
+    set_entry_status(
+        &mut ctx.accounts.authority_registry_page,
+        position.authority_index,
+        auction.key(),
+        status,
+    )?;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(RegistryStatusUpdated {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        status,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+    events.emit_status_change(auction, previous_status, &clock)?;
+
+    Ok(())
+}
+
+/// Writes `status` to the page entry at registry `index`, which must list
+/// `auction`, returning the previous status
+fn set_entry_status(
+    page: &mut RegistryPage,
+    index: u64,
+    auction: Pubkey,
+    status: u8,
+) -> Result<u8> {
+    let (page_index, slot) = registry_location(index);
+    require!(
+        page.index == page_index,
+        SuperpullProgramError::InvalidRegistryPage
+    );
+    let entry = page.entries
+        .get_mut(slot)
+        .filter(|entry| entry.auction == auction)
+        .ok_or(SuperpullProgramError::InvalidRegistryPage)?;
+    let previous_status = entry.status;
+    entry.status = status;
+
+    Ok(previous_status)
+}
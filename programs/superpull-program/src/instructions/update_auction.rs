@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionState, UpdateAuctionArgs},
+    utils::{errors::SuperpullProgramError, events::{AuctionUpdated, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateAuction<'info> {
+    #[account(
+        mut,
+        has_one = authority @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub auction: Account<'info, AuctionState>,
+
+    /// The authority who manages the auction
+    pub authority: Signer<'info>,
+}
+
+pub fn update_auction_handler(ctx: Context<UpdateAuction>, args: UpdateAuctionArgs) -> Result<()> {
+    // Multisig auctions update through approved proposals
+    require!(
+        !ctx.accounts.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    apply_auction_update(&mut ctx.accounts.auction, args, &events)
+}
+
+/// Applies the parameter updates, shared by `update_auction` and approved
+/// multisig proposals
+pub fn apply_auction_update(
+    auction: &mut Account<AuctionState>,
+    args: UpdateAuctionArgs,
+    events: &EventEmitter,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+
+    if let Some(deadline) = args.deadline {
+        check_deadline_extension(auction, deadline, clock.unix_timestamp)?;
+        auction.deadline = deadline;
+    }
+
+    // A zero limit means bids are not capped per wallet
+    if let Some(max_per_wallet) = args.max_per_wallet {
+        require!(
+            max_per_wallet <= auction.max_supply,
+            SuperpullProgramError::InvalidMaxPerWallet
+        );
+        auction.max_per_wallet = max_per_wallet;
+    }
+
+    if let Some(treasury) = args.treasury {
+        require!(
+            !treasury.eq(&Pubkey::default()),
+            SuperpullProgramError::InvalidTreasury
+        );
+        auction.treasury = treasury;
+    }
+
+    events.emit(AuctionUpdated {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        deadline: auction.deadline,
+        max_per_wallet: auction.max_per_wallet,
+        treasury: auction.treasury,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
+
+/// Checks the deadline can move to `deadline` at `now`: it can only be
+/// extended, before it passes and before the first item is sold, so bidders
+/// are never held past the deadline they bid under
+pub fn check_deadline_extension(auction: &AuctionState, deadline: i64, now: i64) -> Result<()> {
+    require!(
+        deadline > auction.deadline && now <= auction.deadline,
+        SuperpullProgramError::InvalidDeadline
+    );
+    require!(
+        auction.current_supply == 0 && auction.last_item_serial == 0,
+        SuperpullProgramError::DeadlineLocked
+    );
+    Ok(())
+}
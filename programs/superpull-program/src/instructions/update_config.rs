@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{GlobalConfig, MAX_ALLOWED_MINTS, MAX_PROTOCOL_FEE_BPS},
+    utils::{errors::SuperpullProgramError, events::{ConfigUpdated, EventEmitter, EVENT_VERSION}},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ SuperpullProgramError::InvalidAuthority,
+    )]
+    pub config: Account<'info, GlobalConfig>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn update_config_handler(
+    ctx: Context<UpdateConfig>,
+    new_admin: Option<Pubkey>,
+    treasury: Option<Pubkey>,
+    protocol_fee_bps: Option<u16>,
+    allowed_mints: Option<Vec<Pubkey>>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(new_admin) = new_admin {
+        require!(
+            !new_admin.eq(&Pubkey::default()),
+            SuperpullProgramError::InvalidAuthority
+        );
+        config.admin = new_admin;
+    }
+
+    if let Some(treasury) = treasury {
+        require!(
+            !treasury.eq(&Pubkey::default()),
+            SuperpullProgramError::InvalidAuthority
+        );
+        config.treasury = treasury;
+    }
+
+    if let Some(protocol_fee_bps) = protocol_fee_bps {
+        require!(
+            protocol_fee_bps <= MAX_PROTOCOL_FEE_BPS,
+            SuperpullProgramError::InvalidProtocolFee
+        );
+        config.protocol_fee_bps = protocol_fee_bps;
+    }
+
+    if let Some(allowed_mints) = allowed_mints {
+        require!(
+            allowed_mints.len() <= MAX_ALLOWED_MINTS,
+            SuperpullProgramError::TooManyAllowedMints
+        );
+        config.allowed_mints = allowed_mints;
+    }
+
+    let clock = Clock::get()?;
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(ConfigUpdated {
+        version: EVENT_VERSION,
+        admin: config.admin,
+        treasury: config.treasury,
+        protocol_fee_bps: config.protocol_fee_bps,
+        allowed_mints: config.allowed_mints.clone(),
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    Ok(())
+}
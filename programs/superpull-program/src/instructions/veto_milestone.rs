@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{AuctionMilestones, AuctionState, BidState},
+    utils::{
+        errors::SuperpullProgramError,
+        events::{AuctionHalted, EventEmitter, MilestoneVetoed, EVENT_VERSION},
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VetoMilestone<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    #[account(
+        mut,
+        seeds = [b"milestones", auction.key().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Account<'info, AuctionMilestones>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bid",
+            auction.key().as_ref(),
+            bidder.key().as_ref(),
+        ],
+        bump = bid.bump,
+        has_one = auction,
+        has_one = bidder,
+    )]
+    pub bid: Account<'info, BidState>,
+
+    pub bidder: Signer<'info>,
+}
+
+pub fn veto_milestone_handler(ctx: Context<VetoMilestone>, milestone_index: u8) -> Result<()> {
+    let auction = &ctx.accounts.auction;
+    let bid = &ctx.accounts.bid;
+    let index = milestone_index as usize;
+
+    // Validate auction state
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+
+    // Only submitted milestones within their challenge window can be vetoed
+    let milestones = &mut ctx.accounts.milestones;
+    let milestone = *milestones
+        .milestones
+        .get(index)
+        .ok_or(SuperpullProgramError::InvalidMilestoneConfig)?;
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    require!(
+        milestone.is_submitted() && current_time < milestones.challenge_end(&milestone)?,
+        SuperpullProgramError::ChallengeWindowClosed
+    );
+
+    // Votes are weighted by the number of items held
+    require!(bid.count > 0, SuperpullProgramError::InvalidBidder);
+    require!(
+        !bid.has_vetoed(index),
+        SuperpullProgramError::AlreadyVetoed
+    );
+
+    // Record the vote
+    let weight = bid.count;
+    let milestone = &mut milestones.milestones[index];
+    milestone.veto_weight = milestone.veto_weight
+        .checked_add(weight)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    let veto_weight = milestone.veto_weight;
+
+    let bid = &mut ctx.accounts.bid;
+    bid.vetoed_milestones |= 1 << index;
+
+    let events = EventEmitter::new(&ctx.accounts.event_authority, ctx.bumps.event_authority);
+    events.emit(MilestoneVetoed {
+        version: EVENT_VERSION,
+        auction: auction.key(),
+        milestone_index,
+        bidder: bid.bidder,
+        weight,
+        veto_weight,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
+
+    // Halt the auction once the veto passes, the remaining escrow becomes
+    // refundable pro rata to each bidder's contribution
+    if milestones.veto_passes(veto_weight, auction.current_supply) {
+        let auction = &mut ctx.accounts.auction;
+        let previous_status = auction.registry_status(current_time);
+        auction.is_halted = true;
+        auction.refund_pool = auction.total_value_locked;
+        auction.refund_basis = auction.total_proceeds()?;
+
+        events.emit(AuctionHalted {
+            version: EVENT_VERSION,
+            auction: auction.key(),
+            milestone_index,
+            refund_pool: auction.refund_pool,
+            refund_basis: auction.refund_basis,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        })?;
+        events.emit_status_change(auction, previous_status, &clock)?;
+    }
+
+    Ok(())
+}
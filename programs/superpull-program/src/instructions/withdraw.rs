@@ -1,28 +1,68 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token;
+use anchor_spl::token::{self, Token};
 use crate::{
-    state::AuctionState,
+    instructions::withdrawable_amount,
+    state::{fee_amount, AuctionMilestones, AuctionState, GlobalConfig},
     utils::errors::SuperpullProgramError,
-    utils::events::FundsWithdrawn,
+    utils::events::{EventEmitter, FundsWithdrawn, PayeePaid, ProtocolFeeCollected, EVENT_VERSION},
 };
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
+    pub funds: WithdrawFunds<'info>,
+
+    /// The authority who authorizes the withdrawal
+    #[account(address = funds.auction.authority @ SuperpullProgramError::UnauthorizedWithdraw)]
+    pub authority: Signer<'info>,
+}
+
+/// Accounts of the withdraw transfers, shared by `withdraw` and approved
+/// multisig withdraw proposals
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawFunds<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, AuctionState>,
+
+    /// Protocol configuration, holds the fee treasury
     #[account(
-        mut,
-        has_one = authority @ SuperpullProgramError::UnauthorizedWithdraw,
+        seeds = [b"config"],
+        bump = config.bump,
     )]
-    pub auction: Account<'info, AuctionState>,
+    pub config: Box<Account<'info, GlobalConfig>>,
 
-    /// The authority who can authorize the withdrawal and receive the funds
-    /// CHECK: Just checking against auction authority
-    #[account(mut)]
-    pub authority: AccountInfo<'info>,
+    /// Release milestones, required when the auction has milestones
+    #[account(
+        seeds = [b"milestones", auction.key().as_ref()],
+        bump = milestones.bump,
+    )]
+    pub milestones: Option<Box<Account<'info, AuctionMilestones>>>,
 
-    /// The authority's token account to receive the withdrawn tokens
-    /// CHECK: Validated through token program CPI
-    #[account(mut)]
-    pub authority_token_account: AccountInfo<'info>,
+    /// The auction treasury's token account to receive the withdrawn tokens,
+    /// required when the auction has no revenue split. Without a treasury
+    /// the proceeds go to the authority
+    /// CHECK: Validated through token program CPI and constraint
+    #[account(
+        mut,
+        constraint = *authority_token_account.owner == token_program.key(),
+        constraint = token::TokenAccount::try_deserialize(&mut &authority_token_account.data.borrow()[..])
+            .is_ok_and(|token_account| {
+                token_account.mint == auction.token_mint && token_account.owner == auction.payout_wallet()
+            }) @ SuperpullProgramError::InvalidTokenAccount
+    )]
+    pub authority_token_account: Option<AccountInfo<'info>>,
+
+    /// The protocol treasury's token account to receive the fee
+    /// CHECK: Validated through token program CPI and constraint
+    #[account(
+        mut,
+        constraint = *treasury_token_account.owner == token_program.key(),
+        constraint = token::TokenAccount::try_deserialize(&mut &treasury_token_account.data.borrow()[..])
+            .is_ok_and(|token_account| {
+                token_account.mint == auction.token_mint && token_account.owner == config.treasury
+            }) @ SuperpullProgramError::InvalidTokenAccount
+    )]
+    pub treasury_token_account: AccountInfo<'info>,
 
     /// The auction's token account to withdraw from
     /// CHECK: Validated through token program CPI
@@ -35,61 +75,219 @@ pub struct Withdraw<'info> {
 
     pub system_program: Program<'info, System>,
 
-    /// CHECK: Validated through token program CPI
-    pub token_program: AccountInfo<'info>,
+    pub token_program: Program<'info, Token>,
 }
 
-pub fn withdraw_handler(ctx: Context<Withdraw>) -> Result<()> {
-    let auction = &ctx.accounts.auction;
-    
+/// Withdraws `amount` of the unlocked proceeds, or all of them when unset.
+///
+/// Remaining accounts: one token account per payee, in the auction's payee
+/// order, when the auction has a revenue split
+pub fn withdraw_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+    amount: Option<u64>,
+) -> Result<()> {
+    // Multisig auctions withdraw through approved proposals
+    require!(
+        !ctx.accounts.funds.auction.has_multisig(),
+        SuperpullProgramError::MultisigRequired
+    );
+
+    let events = EventEmitter::new(
+        &ctx.accounts.funds.event_authority,
+        ctx.bumps.funds.event_authority,
+    );
+    process_withdraw(&mut ctx.accounts.funds, ctx.remaining_accounts, amount, &events)
+}
+
+/// Transfers the withdrawn proceeds, shared by `withdraw` and approved
+/// multisig withdraw proposals
+pub fn process_withdraw<'info>(
+    accounts: &mut WithdrawFunds<'info>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    amount: Option<u64>,
+    events: &EventEmitter,
+) -> Result<()> {
+    let auction = &accounts.auction;
+
     // Validate auction state
     require!(
         auction.is_graduated,
         SuperpullProgramError::NotGraduated
     );
 
+    // Funds of a halted auction are refunded to the backers
+    require!(
+        !auction.is_halted,
+        SuperpullProgramError::AuctionHalted
+    );
+
+    // Withdrawals are blocked while the arbiter reviews a dispute
+    require!(
+        !auction.withdrawals_frozen,
+        SuperpullProgramError::WithdrawalsFrozen
+    );
+
+    // Funds stay locked until the seller confirms delivery
+    require!(
+        !auction.is_delivery_pending(),
+        SuperpullProgramError::DeliveryNotConfirmed
+    );
+
     // Validate authority
     require!(
-        !ctx.accounts.authority.key().eq(&Pubkey::default()),
+        !auction.authority.eq(&Pubkey::default()),
         SuperpullProgramError::InvalidAuthority
     );
 
-    // Get the amount to withdraw
-    let amount = auction.total_value_locked;
+    // Get the amount to withdraw, only the unlocked portion is available
+    let token_program = accounts.token_program.to_account_info();
+    let clock = Clock::get()?;
+    let withdrawable = withdrawable_amount(
+        auction,
+        accounts.milestones.as_deref().map(|milestones| &**milestones),
+        clock.unix_timestamp,
+    )?;
+    let amount = amount.unwrap_or(withdrawable);
     require!(amount > 0, SuperpullProgramError::NoFundsToWithdraw);
+    require!(
+        amount <= withdrawable,
+        SuperpullProgramError::ExcessiveWithdrawalAmount
+    );
 
-    // Transfer tokens from auction account to authority account
-    let seeds = &[
-        b"auction",
-        auction.authority.as_ref(),
-        auction.collection_mint.as_ref(),
-        &[auction.bump],
-    ];
-    let signer = &[&seeds[..]];
+    // Split the protocol fee from the seller's share
+    let protocol_fee = fee_amount(amount, auction.protocol_fee_bps)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    let seller_amount = amount
+        .checked_sub(protocol_fee)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
 
-    let cpi_accounts = token::Transfer {
-        from: ctx.accounts.auction_token_account.to_account_info(),
-        to: ctx.accounts.authority_token_account.to_account_info(),
-        authority: ctx.accounts.auction.to_account_info(),
-    };
+    // Transfer the fee to the protocol treasury
+    if protocol_fee > 0 {
+        transfer_from_auction(
+            auction,
+            &accounts.auction_token_account,
+            &accounts.treasury_token_account,
+            &token_program,
+            protocol_fee,
+        )?;
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer,
-    );
-    token::transfer(cpi_ctx, amount)?;
+        events.emit(ProtocolFeeCollected {
+            version: EVENT_VERSION,
+            auction: auction.key(),
+            treasury: accounts.config.treasury,
+            token_mint: auction.token_mint,
+            amount: protocol_fee,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        })?;
+    }
+
+    // Transfer the remaining tokens to the treasury account, or split them
+    // between the payees
+    if auction.payees.is_empty() {
+        let authority_token_account = accounts.authority_token_account
+            .as_ref()
+            .ok_or(SuperpullProgramError::InvalidTokenAccount)?;
+        if seller_amount > 0 {
+            transfer_from_auction(
+                auction,
+                &accounts.auction_token_account,
+                authority_token_account,
+                &token_program,
+                seller_amount,
+            )?;
+        }
+    } else {
+        let payee_amounts = auction.payee_amounts(seller_amount)?;
+        require!(
+            remaining_accounts.len() >= auction.payees.len(),
+            SuperpullProgramError::PayeeAccountsMissing
+        );
+
+        for ((payee, payee_amount), payee_token_account) in auction.payees
+            .iter()
+            .zip(payee_amounts)
+            .zip(remaining_accounts.iter())
+        {
+            // Validate payee token account
+            require!(
+                *payee_token_account.owner == token_program.key(),
+                SuperpullProgramError::InvalidTokenAccount
+            );
+            let token_account = token::TokenAccount::try_deserialize(
+                &mut &payee_token_account.data.borrow()[..],
+            )?;
+            require!(
+                token_account.mint == auction.token_mint && token_account.owner == payee.wallet,
+                SuperpullProgramError::InvalidTokenAccount
+            );
+
+            if payee_amount > 0 {
+                transfer_from_auction(
+                    auction,
+                    &accounts.auction_token_account,
+                    payee_token_account,
+                    &token_program,
+                    payee_amount,
+                )?;
+            }
+
+            events.emit(PayeePaid {
+                version: EVENT_VERSION,
+                auction: auction.key(),
+                payee: payee.wallet,
+                share_bps: payee.share_bps,
+                amount: payee_amount,
+                timestamp: clock.unix_timestamp,
+                slot: clock.slot,
+            })?;
+        }
+    }
 
     // Update auction state
-    let auction = &mut ctx.accounts.auction;
-    auction.total_value_locked = 0;
+    let auction = &mut accounts.auction;
+    auction.total_value_locked = auction.total_value_locked
+        .checked_sub(amount)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
+    auction.total_withdrawn = auction.total_withdrawn
+        .checked_add(amount)
+        .ok_or(SuperpullProgramError::MathOverflow)?;
 
     // Emit withdraw event
-    emit!(FundsWithdrawn {
+    events.emit(FundsWithdrawn {
+        version: EVENT_VERSION,
         auction: auction.key(),
-        authority: ctx.accounts.authority.key(),
-        amount,
-    });
+        authority: auction.authority,
+        amount: seller_amount,
+        protocol_fee,
+        remaining: auction.total_value_locked,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot,
+    })?;
 
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Transfers tokens out of the auction's escrow, signed by the auction PDA
+pub fn transfer_from_auction<'info>(
+    auction: &Account<'info, AuctionState>,
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = token::Transfer {
+        from: from.clone(),
+        to: to.clone(),
+        authority: auction.to_account_info(),
+    };
+
+    auction.with_signer_seeds(|signer| {
+        let cpi_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            cpi_accounts,
+            signer,
+        );
+        token::transfer(cpi_ctx, amount)
+    })
+}
@@ -7,11 +7,34 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::{
+    AllowlistConfig, ArbiterRuling, GateRule, Payee, PhaseArgs, ProposalAction, UpdateAuctionArgs,
+    VestingSchedule,
+};
 
 #[program]
 pub mod superpull_program {
     use super::*;
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        protocol_fee_bps: u16,
+        allowed_mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        initialize_config_handler(ctx, protocol_fee_bps, allowed_mints)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        new_admin: Option<Pubkey>,
+        treasury: Option<Pubkey>,
+        protocol_fee_bps: Option<u16>,
+        allowed_mints: Option<Vec<Pubkey>>,
+    ) -> Result<()> {
+        update_config_handler(ctx, new_admin, treasury, protocol_fee_bps, allowed_mints)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_auction(
         ctx: Context<InitializeAuction>,
         base_price: u64,
@@ -19,23 +42,200 @@ pub mod superpull_program {
         max_supply: u64,
         minimum_items: u64,
         deadline: i64,
+        max_per_wallet: u64,
+        allowlist: Option<AllowlistConfig>,
+        gate: Option<GateRule>,
+        payees: Vec<Payee>,
+        dust_payee_index: u8,
+        referral_bps: u16,
+        vesting: Option<VestingSchedule>,
+        delivery_period: Option<i64>,
+        arbiter: Option<Pubkey>,
+        treasury: Option<Pubkey>,
+        multisig_signers: Vec<Pubkey>,
+        multisig_threshold: u8,
+    ) -> Result<()> {
+        initialize_auction_handler(
+            ctx,
+            base_price,
+            price_increment,
+            max_supply,
+            minimum_items,
+            deadline,
+            max_per_wallet,
+            allowlist,
+            gate,
+            payees,
+            dust_payee_index,
+            referral_bps,
+            vesting,
+            delivery_period,
+            arbiter,
+            treasury,
+            multisig_signers,
+            multisig_threshold,
+        )
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        propose_authority_handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        accept_authority_handler(ctx)
+    }
+
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+        set_treasury_handler(ctx, treasury)
+    }
+
+    pub fn set_sell_back(ctx: Context<SetSellBack>, spread_bps: Option<u16>) -> Result<()> {
+        set_sell_back_handler(ctx, spread_bps)
+    }
+
+    pub fn update_auction(ctx: Context<UpdateAuction>, args: UpdateAuctionArgs) -> Result<()> {
+        update_auction_handler(ctx, args)
+    }
+
+    pub fn cancel_auction(ctx: Context<CancelAuction>) -> Result<()> {
+        cancel_auction_handler(ctx)
+    }
+
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+        create_proposal_handler(ctx, action)
+    }
+
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        approve_proposal_handler(ctx)
+    }
+
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        execute_proposal_handler(ctx)
+    }
+
+    pub fn execute_withdraw_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteWithdrawProposal<'info>>,
     ) -> Result<()> {
-        initialize_auction_handler(ctx, base_price, price_increment, max_supply, minimum_items, deadline)
+        execute_withdraw_proposal_handler(ctx)
+    }
+
+    pub fn execute_phases_proposal(
+        ctx: Context<ExecutePhasesProposal>,
+        phases: Vec<PhaseArgs>,
+    ) -> Result<()> {
+        execute_phases_proposal_handler(ctx, phases)
+    }
+
+    pub fn execute_milestones_proposal(
+        ctx: Context<ExecuteMilestonesProposal>,
+        release_bps: Vec<u16>,
+        challenge_period: i64,
+        veto_threshold_bps: u16,
+    ) -> Result<()> {
+        execute_milestones_proposal_handler(ctx, release_bps, challenge_period, veto_threshold_bps)
+    }
+
+    pub fn set_phases(ctx: Context<SetPhases>, phases: Vec<PhaseArgs>) -> Result<()> {
+        set_phases_handler(ctx, phases)
+    }
+
+    pub fn set_milestones(
+        ctx: Context<SetMilestones>,
+        release_bps: Vec<u16>,
+        challenge_period: i64,
+        veto_threshold_bps: u16,
+    ) -> Result<()> {
+        set_milestones_handler(ctx, release_bps, challenge_period, veto_threshold_bps)
+    }
+
+    pub fn submit_milestone(ctx: Context<SubmitMilestone>, evidence_hash: [u8; 32]) -> Result<()> {
+        submit_milestone_handler(ctx, evidence_hash)
+    }
+
+    pub fn veto_milestone(ctx: Context<VetoMilestone>, milestone_index: u8) -> Result<()> {
+        veto_milestone_handler(ctx, milestone_index)
     }
 
     pub fn get_current_price(ctx: Context<GetCurrentPrice>) -> Result<()> {
         get_current_price_handler(ctx)
     }
 
-    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
-        place_bid_handler(ctx, amount)
+    pub fn place_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PlaceBid<'info>>,
+        amount: u64,
+        allowlist_proof: Option<AllowlistProof>,
+    ) -> Result<()> {
+        place_bid_handler(ctx, amount, allowlist_proof)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        register_referrer_handler(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        claim_referral_rewards_handler(ctx)
     }
 
-    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
-        withdraw_handler(ctx)
+    pub fn confirm_delivery(ctx: Context<ConfirmDelivery>) -> Result<()> {
+        confirm_delivery_handler(ctx)
     }
 
-    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+    pub fn arbitrate(
+        ctx: Context<Arbitrate>,
+        ruling: ArbiterRuling,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        arbitrate_handler(ctx, ruling, reason_hash)
+    }
+
+    pub fn get_withdrawable(ctx: Context<GetWithdrawable>) -> Result<()> {
+        get_withdrawable_handler(ctx)
+    }
+
+    pub fn withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Withdraw<'info>>,
+        amount: Option<u64>,
+    ) -> Result<()> {
+        withdraw_handler(ctx, amount)
+    }
+
+    pub fn refund<'info>(ctx: Context<'_, '_, 'info, 'info, Refund<'info>>) -> Result<()> {
         refund_handler(ctx)
     }
+
+    pub fn refund_item<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundItem<'info>>,
+        leaf: ItemLeaf,
+    ) -> Result<()> {
+        refund_item_handler(ctx, leaf)
+    }
+
+    pub fn refund_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RefundBatch<'info>>,
+        bounty: u64,
+    ) -> Result<()> {
+        refund_batch_handler(ctx, bounty)
+    }
+
+    pub fn sell_back<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SellBack<'info>>,
+        leaf: ItemLeaf,
+    ) -> Result<()> {
+        sell_back_handler(ctx, leaf)
+    }
+
+    pub fn sync_registry(ctx: Context<SyncRegistry>) -> Result<()> {
+        sync_registry_handler(ctx)
+    }
+
+    pub fn migrate_auction(ctx: Context<MigrateAuction>) -> Result<()> {
+        migrate_auction_handler(ctx)
+    }
+
+    pub fn migrate_bid(ctx: Context<MigrateBid>) -> Result<()> {
+        migrate_bid_handler(ctx)
+    }
 }
@@ -1,4 +1,87 @@
 use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        fee_amount, GateRule, RegistryPosition, BPS_DENOMINATOR, MAX_MULTISIG_SIGNERS,
+        REGISTRY_STATUS_ACTIVE, REGISTRY_STATUS_EXPIRED, REGISTRY_STATUS_GRADUATED,
+        REGISTRY_STATUS_HALTED, REGISTRY_STATUS_SOLD_OUT,
+    },
+    utils::errors::SuperpullProgramError,
+};
+
+pub const MAX_PAYEES: usize = 5;
+pub const MAX_SELL_BACK_SPREAD_BPS: u16 = 2_500;
+
+/// Allowlist presale that runs from initialization until `end_time`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AllowlistConfig {
+    /// Root of the merkle tree of `(bidder, allocation)` leaves
+    pub merkle_root: [u8; 32],
+    /// Time at which bidding opens to everyone
+    pub end_time: i64,
+    /// Discounted price for allowlisted bids, the curve price applies if unset
+    pub price: Option<u64>,
+}
+
+impl AllowlistConfig {
+    pub const LEN: usize = 32 + // merkle_root
+        8 + // end_time
+        1 + 8; // price
+}
+
+/// Recipient of a share of the seller's proceeds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Payee {
+    pub wallet: Pubkey,
+    pub share_bps: u16,
+}
+
+impl Payee {
+    pub const LEN: usize = 32 + // wallet
+        2; // share_bps
+}
+
+/// Linear release of the seller's proceeds, counted from graduation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VestingSchedule {
+    /// Seconds after graduation before anything can be withdrawn
+    pub cliff: i64,
+    /// Seconds after graduation until the proceeds are fully unlocked
+    pub duration: i64,
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 8 + // cliff
+        8; // duration
+
+    /// Portion of `total` unlocked `elapsed` seconds after graduation
+    pub fn vested_amount(&self, total: u64, elapsed: i64) -> Option<u64> {
+        if elapsed < self.cliff {
+            return Some(0);
+        }
+        if elapsed >= self.duration {
+            return Some(total);
+        }
+        let vested = (total as u128)
+            .checked_mul(elapsed as u128)?
+            .checked_div(self.duration as u128)?;
+        u64::try_from(vested).ok()
+    }
+}
+
+/// Auction parameters that can change after creation, unset fields are kept
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct UpdateAuctionArgs {
+    /// New deadline, can only be extended before the first item is sold
+    pub deadline: Option<i64>,
+    pub max_per_wallet: Option<u64>,
+    pub treasury: Option<Pubkey>,
+}
+
+impl UpdateAuctionArgs {
+    pub const LEN: usize = 1 + 8 + // deadline
+        1 + 8 + // max_per_wallet
+        1 + 32; // treasury
+}
 
 #[account]
 pub struct AuctionState {
@@ -15,6 +98,43 @@ pub struct AuctionState {
     pub deadline: i64,
     pub is_graduated: bool,
     pub bump: u8,
+    pub max_per_wallet: u64,
+    pub allowlist: Option<AllowlistConfig>,
+    pub has_phases: bool,
+    pub gate: Option<GateRule>,
+    pub protocol_fee_bps: u16,
+    pub payees: Vec<Payee>,
+    pub dust_payee_index: u8,
+    pub referral_bps: u16,
+    pub referral_fees_owed: u64,
+    pub vesting: Option<VestingSchedule>,
+    pub graduated_at: i64,
+    pub total_withdrawn: u64,
+    pub has_milestones: bool,
+    pub is_halted: bool,
+    pub refund_pool: u64,
+    pub refund_basis: u64,
+    pub delivery_period: Option<i64>,
+    pub delivery_confirmed: bool,
+    pub arbiter: Option<Pubkey>,
+    pub withdrawals_frozen: bool,
+    pub ruling_count: u32,
+    pub creator: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub treasury: Pubkey,
+    pub multisig_signers: Vec<Pubkey>,
+    pub multisig_threshold: u8,
+    pub proposal_count: u32,
+    pub auction_id: u64,
+    /// Position in the auction registries, unset for auctions created before
+    /// the registry existed
+    pub registry_position: Option<RegistryPosition>,
+    /// Spread kept by the seller when a bidder sells an item back to the
+    /// curve, sell-backs are disabled when unset
+    pub sell_back_spread_bps: Option<u16>,
+    /// Serial of the last item sold. Sell-backs lower the supply, so serials
+    /// continue from here. Zero for auctions created before sell-backs
+    pub last_item_serial: u64,
 }
 
 impl AuctionState {
@@ -31,5 +151,254 @@ impl AuctionState {
         8 + // minimum_items
         8 + // deadline
         1 + // is_graduated
-        1; // bump
-} 
\ No newline at end of file
+        1 + // bump
+        8 + // max_per_wallet
+        1 + AllowlistConfig::LEN + // allowlist
+        1 + // has_phases
+        1 + GateRule::LEN + // gate
+        2 + // protocol_fee_bps
+        4 + MAX_PAYEES * Payee::LEN + // payees
+        1 + // dust_payee_index
+        2 + // referral_bps
+        8 + // referral_fees_owed
+        1 + VestingSchedule::LEN + // vesting
+        8 + // graduated_at
+        8 + // total_withdrawn
+        1 + // has_milestones
+        1 + // is_halted
+        8 + // refund_pool
+        8 + // refund_basis
+        1 + 8 + // delivery_period
+        1 + // delivery_confirmed
+        1 + 32 + // arbiter
+        1 + // withdrawals_frozen
+        4 + // ruling_count
+        32 + // creator
+        1 + 32 + // pending_authority
+        32 + // treasury
+        4 + MAX_MULTISIG_SIGNERS * 32 + // multisig_signers
+        1 + // multisig_threshold
+        4 + // proposal_count
+        8 + // auction_id
+        1 + RegistryPosition::LEN + // registry_position
+        1 + 2 + // sell_back_spread_bps
+        8; // last_item_serial
+
+    /// Authority the auction PDA is derived from. Auctions created before
+    /// authority transfers only store it in `authority`
+    pub fn seed_authority(&self) -> &Pubkey {
+        if self.creator == Pubkey::default() {
+            &self.authority
+        } else {
+            &self.creator
+        }
+    }
+
+    /// Auction id seed of the PDA. Auctions created before ids existed have
+    /// id 0 and an empty seed, which derives the same address as omitting it
+    pub fn auction_id_seed(&self) -> Vec<u8> {
+        if self.auction_id == 0 {
+            Vec::new()
+        } else {
+            self.auction_id.to_le_bytes().to_vec()
+        }
+    }
+
+    /// Serial number of the next item sold, serials start at 1
+    pub fn next_item_serial(&self) -> u64 {
+        self.current_supply.max(self.last_item_serial).saturating_add(1)
+    }
+
+    /// Calls `f` with the seeds of the auction PDA, used to sign for the
+    /// escrow and the tree
+    pub fn with_signer_seeds<R>(&self, f: impl FnOnce(&[&[&[u8]]]) -> R) -> R {
+        let auction_id_seed = self.auction_id_seed();
+        f(&[&[
+            b"auction",
+            self.seed_authority().as_ref(),
+            self.collection_mint.as_ref(),
+            &auction_id_seed,
+            std::slice::from_ref(&self.bump),
+        ]])
+    }
+
+    /// Whether sensitive actions need the approval of the signer set
+    pub fn has_multisig(&self) -> bool {
+        self.multisig_threshold > 0
+    }
+
+    /// Index of `signer` in the multisig signer set
+    pub fn multisig_signer_index(&self, signer: &Pubkey) -> Option<usize> {
+        self.multisig_signers.iter().position(|key| key == signer)
+    }
+
+    /// Wallet that receives the seller's proceeds
+    pub fn payout_wallet(&self) -> Pubkey {
+        if self.treasury == Pubkey::default() {
+            self.authority
+        } else {
+            self.treasury
+        }
+    }
+
+    /// Price of the next item on the public bonding curve
+    pub fn current_price(&self) -> Result<u64> {
+        self.price_increment
+            .checked_mul(self.current_supply)
+            .and_then(|increment| increment.checked_add(self.base_price))
+            .ok_or(SuperpullProgramError::MathOverflow.into())
+    }
+
+    /// Price an item is sold back at: the curve price of the last item sold,
+    /// capped at `paid` so the escrow still covers the other bids in full
+    pub fn sell_back_price(&self, paid: u64) -> Result<u64> {
+        let last_price = self.price_increment
+            .checked_mul(self.current_supply.saturating_sub(1))
+            .and_then(|increment| increment.checked_add(self.base_price))
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        Ok(last_price.min(paid))
+    }
+
+    /// Allowlist configuration if the presale is still running at `now`
+    pub fn active_allowlist(&self, now: i64) -> Option<AllowlistConfig> {
+        self.allowlist.filter(|allowlist| now < allowlist.end_time)
+    }
+
+    /// Proceeds raised so far, withdrawn or still in escrow
+    pub fn total_proceeds(&self) -> Result<u64> {
+        self.total_value_locked
+            .checked_add(self.total_withdrawn)
+            .ok_or(SuperpullProgramError::MathOverflow.into())
+    }
+
+    /// Whether the seller still has to confirm delivery before funds are released
+    pub fn is_delivery_pending(&self) -> bool {
+        self.delivery_period.is_some() && !self.delivery_confirmed
+    }
+
+    /// Time by which the seller must confirm delivery of a graduated auction
+    pub fn delivery_deadline(&self) -> Option<i64> {
+        self.delivery_period
+            .filter(|_| self.is_graduated)
+            .map(|period| self.graduated_at.saturating_add(period))
+    }
+
+    /// Whether the seller missed the delivery deadline at `now`
+    pub fn is_delivery_failed(&self, now: i64) -> bool {
+        !self.delivery_confirmed && self.delivery_deadline().is_some_and(|deadline| now > deadline)
+    }
+
+    /// Proceeds unlocked at `now`, everything unlocks at graduation without a
+    /// vesting schedule
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        let total = self.total_proceeds()?;
+        if !self.is_graduated {
+            return Ok(0);
+        }
+        match self.vesting {
+            Some(vesting) => {
+                let elapsed = now.saturating_sub(self.graduated_at);
+                vesting
+                    .vested_amount(total, elapsed)
+                    .ok_or(SuperpullProgramError::MathOverflow.into())
+            }
+            None => Ok(total),
+        }
+    }
+
+    /// Unlocked proceeds that have not been withdrawn yet
+    pub fn withdrawable_amount(&self, now: i64) -> Result<u64> {
+        Ok(self
+            .vested_amount(now)?
+            .saturating_sub(self.total_withdrawn)
+            .min(self.total_value_locked))
+    }
+
+    /// Share of the escrow refunded to a bidder of a halted auction, pro rata
+    /// to the bidder's net contribution
+    pub fn halted_refund_amount(&self, contribution: u64) -> Result<u64> {
+        if self.refund_basis == 0 {
+            return Ok(0);
+        }
+        let amount = (contribution as u128)
+            .checked_mul(self.refund_pool as u128)
+            .and_then(|amount| amount.checked_div(self.refund_basis as u128))
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        u64::try_from(amount).map_err(|_| SuperpullProgramError::MathOverflow.into())
+    }
+
+    /// Registry status flags of the auction at `now`
+    pub fn registry_status(&self, now: i64) -> u8 {
+        let mut status = 0;
+        let halted = self.is_halted || self.is_delivery_failed(now);
+        let expired = !self.is_graduated && now > self.deadline;
+        let sold_out = self.current_supply >= self.max_supply;
+        if self.is_graduated {
+            status |= REGISTRY_STATUS_GRADUATED;
+        }
+        if sold_out {
+            status |= REGISTRY_STATUS_SOLD_OUT;
+        }
+        if expired {
+            status |= REGISTRY_STATUS_EXPIRED;
+        }
+        if halted {
+            status |= REGISTRY_STATUS_HALTED;
+        }
+        if !halted && !expired && !sold_out {
+            status |= REGISTRY_STATUS_ACTIVE;
+        }
+        status
+    }
+
+    /// Splits `amount` between the payees by share, rounding dust goes to
+    /// the designated dust payee
+    pub fn payee_amounts(&self, amount: u64) -> Result<Vec<u64>> {
+        let mut amounts = self.payees
+            .iter()
+            .map(|payee| fee_amount(amount, payee.share_bps))
+            .collect::<Option<Vec<u64>>>()
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+
+        let distributed = amounts
+            .iter()
+            .try_fold(0u64, |total, share| total.checked_add(*share))
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        let dust = amount
+            .checked_sub(distributed)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        let dust_payee = amounts
+            .get_mut(self.dust_payee_index as usize)
+            .ok_or(SuperpullProgramError::InvalidPayeeConfig)?;
+        *dust_payee = dust_payee
+            .checked_add(dust)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+
+        Ok(amounts)
+    }
+}
+
+/// Validates a revenue split, an empty split pays everything to the authority
+pub fn validate_payees(payees: &[Payee], dust_payee_index: u8) -> Result<()> {
+    if payees.is_empty() {
+        return Ok(());
+    }
+
+    require!(
+        payees.len() <= MAX_PAYEES && (dust_payee_index as usize) < payees.len(),
+        SuperpullProgramError::InvalidPayeeConfig
+    );
+    require!(
+        payees
+            .iter()
+            .all(|payee| payee.share_bps > 0 && !payee.wallet.eq(&Pubkey::default())),
+        SuperpullProgramError::InvalidPayeeConfig
+    );
+    let total_bps: u64 = payees.iter().map(|payee| payee.share_bps as u64).sum();
+    require!(
+        total_bps == BPS_DENOMINATOR,
+        SuperpullProgramError::InvalidPayeeConfig
+    );
+
+    Ok(())
+}
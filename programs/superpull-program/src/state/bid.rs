@@ -1,12 +1,24 @@
 use anchor_lang::prelude::*;
 
+/// Most a cranker can take from the rent of each bid account closed by
+/// `refund_batch`
+pub const MAX_REFUND_BOUNTY_LAMPORTS: u64 = 10_000;
+
 #[account]
 pub struct BidState {
     pub auction: Pubkey,
     pub bidder: Pubkey,
     pub amount: u64,
     pub bump: u8,
-    pub count: u8,
+    pub count: u64,
+    pub allowlist_used: u64,
+    pub phase_index: u8,
+    pub phase_count: u64,
+    pub referral_fees: u64,
+    pub vetoed_milestones: u8,
+    /// Items with a receipt not yet refunded or sold back, these are only
+    /// refunded one by one through `refund_item`
+    pub open_receipts: u64,
 }
 
 impl BidState {
@@ -15,5 +27,25 @@ impl BidState {
         32 + // bidder
         8 +  // amount
         1 +  // bump
-        1;  // count
+        8 +  // count
+        8 +  // allowlist_used
+        1 +  // phase_index
+        8 +  // phase_count
+        8 +  // referral_fees
+        1 +  // vetoed_milestones
+        8;  // open_receipts
+
+    /// Items bought by this wallet during the given phase
+    pub fn phase_count_for(&self, index: usize) -> u64 {
+        if self.phase_index as usize == index {
+            self.phase_count
+        } else {
+            0
+        }
+    }
+
+    /// Whether this bidder already voted to veto the given milestone
+    pub fn has_vetoed(&self, index: usize) -> bool {
+        self.vetoed_milestones & (1 << index) != 0
+    }
 } 
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_ALLOWED_MINTS: usize = 16;
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[account]
+pub struct GlobalConfig {
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub allowed_mints: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl GlobalConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // treasury
+        2 + // protocol_fee_bps
+        4 + MAX_ALLOWED_MINTS * 32 + // allowed_mints
+        1; // bump
+
+    /// An empty allow list accepts any payment mint
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        self.allowed_mints.is_empty() || self.allowed_mints.contains(mint)
+    }
+}
+
+/// Portion of `amount` owed for a fee of `fee_bps`, rounded down
+pub fn fee_amount(amount: u64, fee_bps: u16) -> Option<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)?
+        .checked_div(BPS_DENOMINATOR as u128)?;
+    u64::try_from(fee).ok()
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+/// Holdings a bidder must prove, through remaining accounts, before bidding
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GateRule {
+    /// Bidder must hold at least `min_amount` of `mint`
+    TokenBalance { mint: Pubkey, min_amount: u64 },
+    /// Bidder must hold an NFT of the verified Metaplex collection
+    Collection {
+        collection_mint: Pubkey,
+        /// Each gating NFT can only be used for a single bid
+        one_use_per_nft: bool,
+    },
+}
+
+impl GateRule {
+    pub const LEN: usize = 1 + // variant
+        32 + // mint / collection_mint
+        8; // min_amount (largest variant)
+}
+
+/// Marks a gating NFT as used for an auction
+#[account]
+pub struct GateMarker {
+    pub auction: Pubkey,
+    pub nft_mint: Pubkey,
+    pub bidder: Pubkey,
+    pub bump: u8,
+}
+
+impl GateMarker {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        32 + // nft_mint
+        32 + // bidder
+        1; // bump
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{fee_amount, AuctionState, BPS_DENOMINATOR},
+    utils::errors::SuperpullProgramError,
+};
+
+pub const MAX_MILESTONES: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Milestone {
+    /// Share of the proceeds released by this milestone
+    pub release_bps: u16,
+    /// Time the authority submitted the milestone, zero while pending
+    pub submitted_at: i64,
+    /// Hash of the off-chain evidence for the milestone
+    pub evidence_hash: [u8; 32],
+    /// Items held by the bidders that voted to halt
+    pub veto_weight: u64,
+}
+
+impl Milestone {
+    pub const LEN: usize = 2 + // release_bps
+        8 + // submitted_at
+        32 + // evidence_hash
+        8; // veto_weight
+
+    pub fn is_submitted(&self) -> bool {
+        self.submitted_at != 0
+    }
+}
+
+#[account]
+pub struct AuctionMilestones {
+    pub auction: Pubkey,
+    /// Seconds after submission during which bidders can veto a milestone
+    pub challenge_period: i64,
+    /// Share of the sold items whose holders must veto to halt the auction
+    pub veto_threshold_bps: u16,
+    pub milestones: Vec<Milestone>,
+    pub bump: u8,
+}
+
+impl AuctionMilestones {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        8 + // challenge_period
+        2 + // veto_threshold_bps
+        4 + MAX_MILESTONES * Milestone::LEN + // milestones
+        1; // bump
+
+    /// Time at which the challenge window of a submitted milestone closes
+    pub fn challenge_end(&self, milestone: &Milestone) -> Result<i64> {
+        milestone.submitted_at
+            .checked_add(self.challenge_period)
+            .ok_or(SuperpullProgramError::MathOverflow.into())
+    }
+
+    /// Index of the next milestone to submit, if any are left
+    pub fn next_index(&self) -> Option<usize> {
+        self.milestones.iter().position(|milestone| !milestone.is_submitted())
+    }
+
+    /// Share of the proceeds released by milestones whose challenge window
+    /// has closed at `now`
+    pub fn released_bps(&self, now: i64) -> Result<u16> {
+        let mut released: u16 = 0;
+        for milestone in self.milestones.iter().filter(|milestone| milestone.is_submitted()) {
+            if now < self.challenge_end(milestone)? {
+                break;
+            }
+            released = released
+                .checked_add(milestone.release_bps)
+                .ok_or(SuperpullProgramError::MathOverflow)?;
+        }
+        Ok(released)
+    }
+
+    /// Whether `veto_weight` out of `total_weight` items reaches the veto threshold
+    pub fn veto_passes(&self, veto_weight: u64, total_weight: u64) -> bool {
+        (veto_weight as u128) * (BPS_DENOMINATOR as u128)
+            >= (total_weight as u128) * (self.veto_threshold_bps as u128)
+    }
+
+    /// Unlocked proceeds that have not been withdrawn yet, capped by the
+    /// milestones released at `now`
+    pub fn withdrawable_amount(&self, auction: &AuctionState, now: i64) -> Result<u64> {
+        let released = fee_amount(auction.total_proceeds()?, self.released_bps(now)?)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        Ok(auction
+            .withdrawable_amount(now)?
+            .min(released.saturating_sub(auction.total_withdrawn)))
+    }
+}
@@ -1,5 +1,25 @@
 pub mod auction;
 pub mod bid;
+pub mod config;
+pub mod gate;
+pub mod milestones;
+pub mod multisig;
+pub mod phases;
+pub mod receipt;
+pub mod referrer;
+pub mod registry;
+pub mod ruling;
+pub mod seller;
 
 pub use auction::*;
-pub use bid::*; 
\ No newline at end of file
+pub use bid::*;
+pub use config::*;
+pub use gate::*;
+pub use milestones::*;
+pub use multisig::*;
+pub use phases::*;
+pub use receipt::*;
+pub use referrer::*;
+pub use registry::*;
+pub use ruling::*;
+pub use seller::*; 
\ No newline at end of file
@@ -0,0 +1,111 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+use crate::{
+    state::{PhaseArgs, UpdateAuctionArgs},
+    utils::errors::SuperpullProgramError,
+};
+
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+/// Sensitive auction action that needs the approval of the signer set
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalAction {
+    /// Withdraws `amount` of the unlocked proceeds, or all of them when unset
+    Withdraw { amount: Option<u64> },
+    /// Cancels the auction, making the escrow refundable
+    Cancel,
+    /// Updates the auction parameters
+    Update { args: UpdateAuctionArgs },
+    /// Nominates the next authority, or cancels a pending nomination when unset
+    ProposeAuthority { new_authority: Option<Pubkey> },
+    /// Sets the phases hashed by [`phases_hash`], the phases are passed on
+    /// execution
+    SetPhases { phases_hash: [u8; 32] },
+    /// Sets the milestones hashed by [`milestones_hash`], the milestones are
+    /// passed on execution
+    SetMilestones { milestones_hash: [u8; 32] },
+    /// Enables sell-backs with the given spread, or disables them when unset
+    SetSellBack { spread_bps: Option<u16> },
+}
+
+impl ProposalAction {
+    pub const LEN: usize = 1 + // variant
+        UpdateAuctionArgs::LEN; // args (largest variant)
+}
+
+#[account]
+pub struct Proposal {
+    pub auction: Pubkey,
+    pub index: u32,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    /// Bit `i` is set once the signer at index `i` approved
+    pub approvals: u16,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        4 + // index
+        32 + // proposer
+        ProposalAction::LEN + // action
+        2 + // approvals
+        1 + // executed
+        8 + // created_at
+        1; // bump
+
+    pub fn approval_count(&self) -> u8 {
+        self.approvals.count_ones() as u8
+    }
+
+    pub fn has_approved(&self, signer_index: usize) -> bool {
+        self.approvals & (1 << signer_index) != 0
+    }
+}
+
+/// Hash of the `set_phases` arguments approved by a `SetPhases` proposal
+pub fn phases_hash(phases: &[PhaseArgs]) -> Result<[u8; 32]> {
+    Ok(keccak::hash(&phases.try_to_vec()?).to_bytes())
+}
+
+/// Hash of the `set_milestones` arguments approved by a `SetMilestones`
+/// proposal
+pub fn milestones_hash(
+    release_bps: &[u16],
+    challenge_period: i64,
+    veto_threshold_bps: u16,
+) -> Result<[u8; 32]> {
+    Ok(keccak::hashv(&[
+        &release_bps.try_to_vec()?,
+        &challenge_period.to_le_bytes(),
+        &veto_threshold_bps.to_le_bytes(),
+    ])
+    .to_bytes())
+}
+
+/// Validates a signer set, an empty set leaves the auction to its authority
+pub fn validate_multisig(signers: &[Pubkey], threshold: u8) -> Result<()> {
+    if signers.is_empty() {
+        require!(threshold == 0, SuperpullProgramError::InvalidMultisigConfig);
+        return Ok(());
+    }
+
+    require!(
+        signers.len() <= MAX_MULTISIG_SIGNERS,
+        SuperpullProgramError::InvalidMultisigConfig
+    );
+    require!(
+        threshold > 0 && threshold as usize <= signers.len(),
+        SuperpullProgramError::InvalidMultisigConfig
+    );
+    for (index, signer) in signers.iter().enumerate() {
+        require!(
+            !signer.eq(&Pubkey::default()) && !signers[..index].contains(signer),
+            SuperpullProgramError::InvalidMultisigConfig
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use crate::utils::errors::SuperpullProgramError;
+
+pub const MAX_PHASES: usize = 8;
+
+/// Sale phase parameters provided by the authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PhaseArgs {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub base_price: u64,
+    pub price_increment: u64,
+    /// Items a single wallet can buy in this phase (0 for unlimited)
+    pub max_per_wallet: u64,
+    /// Items allocated to this phase
+    pub supply: u64,
+    /// Restricts the phase to an allowlist when set
+    pub merkle_root: Option<[u8; 32]>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Phase {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub base_price: u64,
+    pub price_increment: u64,
+    pub max_per_wallet: u64,
+    pub supply: u64,
+    pub merkle_root: Option<[u8; 32]>,
+    pub minted: u64,
+    /// Set by the phase's first bid
+    pub has_bids: bool,
+}
+
+impl Phase {
+    pub const LEN: usize = 8 + // start_time
+        8 + // end_time
+        8 + // base_price
+        8 + // price_increment
+        8 + // max_per_wallet
+        8 + // supply
+        1 + 32 + // merkle_root
+        8 + // minted
+        1; // has_bids
+
+    /// Price of the next item on this phase's curve
+    pub fn current_price(&self) -> Result<u64> {
+        self.price_increment
+            .checked_mul(self.minted)
+            .and_then(|increment| increment.checked_add(self.base_price))
+            .ok_or(SuperpullProgramError::MathOverflow.into())
+    }
+
+    pub fn is_active(&self, now: i64) -> bool {
+        self.start_time <= now && now < self.end_time
+    }
+}
+
+impl From<PhaseArgs> for Phase {
+    fn from(args: PhaseArgs) -> Self {
+        Self {
+            start_time: args.start_time,
+            end_time: args.end_time,
+            base_price: args.base_price,
+            price_increment: args.price_increment,
+            max_per_wallet: args.max_per_wallet,
+            supply: args.supply,
+            merkle_root: args.merkle_root,
+            minted: 0,
+            has_bids: false,
+        }
+    }
+}
+
+#[account]
+pub struct AuctionPhases {
+    pub auction: Pubkey,
+    pub phases: Vec<Phase>,
+    pub bump: u8,
+}
+
+impl AuctionPhases {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        4 + MAX_PHASES * Phase::LEN + // phases
+        1; // bump
+
+    /// Index of the phase running at `now`, phases never overlap
+    pub fn active_phase_index(&self, now: i64) -> Option<usize> {
+        self.phases.iter().position(|phase| phase.is_active(now))
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Record of a single item bought in an auction, one per serial number
+#[account]
+pub struct ItemReceipt {
+    pub auction: Pubkey,
+    /// Serial number of the item in the auction, starting at 1
+    pub serial: u64,
+    pub bidder: Pubkey,
+    /// Price paid for the item, including the referral fee
+    pub price: u64,
+    /// Part of the price credited to the bid's referrer
+    pub referral_fee: u64,
+    pub timestamp: i64,
+    /// Index of the item's leaf in the auction's merkle tree
+    pub leaf_index: u64,
+    /// Bubblegum asset id of the item's cNFT
+    pub asset_id: Pubkey,
+    pub bump: u8,
+    /// Referrer credited with the item's referral fee
+    pub referrer: Option<Pubkey>,
+}
+
+impl ItemReceipt {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        8 + // serial
+        32 + // bidder
+        8 + // price
+        8 + // referral_fee
+        8 + // timestamp
+        8 + // leaf_index
+        32 + // asset_id
+        1 + // bump
+        1 + 32; // referrer
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::utils::errors::SuperpullProgramError;
+
+pub const MAX_REFERRAL_BPS: u16 = 2_500;
+
+/// Referral totals of a promoter for a single auction
+#[account]
+pub struct ReferrerState {
+    pub auction: Pubkey,
+    pub referrer: Pubkey,
+    pub referral_count: u64,
+    pub total_volume: u64,
+    pub total_rewards: u64,
+    pub claimed_rewards: u64,
+    pub bump: u8,
+}
+
+impl ReferrerState {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        32 + // referrer
+        8 + // referral_count
+        8 + // total_volume
+        8 + // total_rewards
+        8 + // claimed_rewards
+        1; // bump
+
+    /// Rewards accrued but not yet claimed
+    pub fn claimable_rewards(&self) -> u64 {
+        self.total_rewards.saturating_sub(self.claimed_rewards)
+    }
+
+    /// Removes a refunded or sold-back item of `price` from the totals once
+    /// its `referral_fee` is no longer owed to the referrer
+    pub fn release_item(&mut self, price: u64, referral_fee: u64) -> Result<()> {
+        self.referral_count = self.referral_count
+            .checked_sub(1)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        self.total_volume = self.total_volume
+            .checked_sub(price)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        self.total_rewards = self.total_rewards
+            .checked_sub(referral_fee)
+            .ok_or(SuperpullProgramError::MathOverflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+/// Number of auctions listed per registry page
+pub const REGISTRY_PAGE_SIZE: usize = 32;
+
+/// Auction accepts bids
+pub const REGISTRY_STATUS_ACTIVE: u8 = 1 << 0;
+/// Auction reached its minimum items
+pub const REGISTRY_STATUS_GRADUATED: u8 = 1 << 1;
+/// Every item has been sold
+pub const REGISTRY_STATUS_SOLD_OUT: u8 = 1 << 2;
+/// Deadline passed before graduation, bids are refundable
+pub const REGISTRY_STATUS_EXPIRED: u8 = 1 << 3;
+/// Auction was cancelled, vetoed or refunded by the arbiter
+pub const REGISTRY_STATUS_HALTED: u8 = 1 << 4;
+
+/// Append-only list of auctions, either global (default authority) or of a
+/// single authority
+#[account]
+pub struct Registry {
+    pub authority: Pubkey,
+    /// Number of auctions listed
+    pub auction_count: u64,
+    pub bump: u8,
+}
+
+impl Registry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // auction_count
+        1; // bump
+
+    /// Page the next auction is appended to
+    pub fn current_page(&self) -> u32 {
+        registry_location(self.auction_count).0
+    }
+}
+
+/// Page and slot of the auction at `index` of a registry
+pub fn registry_location(index: u64) -> (u32, usize) {
+    let page_size = REGISTRY_PAGE_SIZE as u64;
+    ((index / page_size) as u32, (index % page_size) as usize)
+}
+
+/// Listed auction with its last synced status flags
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RegistryEntry {
+    pub auction: Pubkey,
+    pub status: u8,
+}
+
+impl RegistryEntry {
+    pub const LEN: usize = 32 + // auction
+        1; // status
+}
+
+/// Fixed-size page of a registry, pages fill up in order
+#[account]
+pub struct RegistryPage {
+    pub registry: Pubkey,
+    pub index: u32,
+    pub entries: Vec<RegistryEntry>,
+    pub bump: u8,
+}
+
+impl RegistryPage {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // registry
+        4 + // index
+        4 + REGISTRY_PAGE_SIZE * RegistryEntry::LEN + // entries
+        1; // bump
+}
+
+/// Position of an auction in the global and its authority's registry
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RegistryPosition {
+    pub global_index: u64,
+    pub authority_index: u64,
+}
+
+impl RegistryPosition {
+    pub const LEN: usize = 8 + // global_index
+        8; // authority_index
+}
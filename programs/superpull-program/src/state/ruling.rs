@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Decision of the auction's arbiter in a dispute
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArbiterRuling {
+    /// Blocks seller withdrawals until released
+    Freeze,
+    /// Halts the auction, the remaining escrow becomes refundable pro rata
+    Refund,
+    /// Lifts a freeze and the delivery lock so the seller can withdraw
+    Release,
+}
+
+/// Record of a single arbiter ruling
+#[account]
+pub struct RulingRecord {
+    pub auction: Pubkey,
+    pub arbiter: Pubkey,
+    pub index: u32,
+    pub ruling: ArbiterRuling,
+    /// Hash of the off-chain reasoning for the ruling
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl RulingRecord {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        32 + // arbiter
+        4 + // index
+        1 + // ruling
+        32 + // reason_hash
+        8 + // timestamp
+        1; // bump
+}
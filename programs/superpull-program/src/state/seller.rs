@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// Hands out auction ids so a seller can run several auctions per collection
+#[account]
+pub struct SellerCounter {
+    pub authority: Pubkey,
+    /// Number of auctions created, also the id of the latest one
+    pub auction_count: u64,
+    pub bump: u8,
+}
+
+impl SellerCounter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // auction_count
+        1; // bump
+
+    /// Id of the next auction, ids start at 1 as 0 marks auctions created
+    /// before ids existed
+    pub fn next_auction_id(&self) -> u64 {
+        self.auction_count.saturating_add(1)
+    }
+}
@@ -17,6 +17,28 @@ pub enum SuperpullProgramError {
     InvalidMinimumItems,
     #[msg("Invalid merkle tree configuration")]
     InvalidMerkleTree,
+    #[msg("Per-wallet limit cannot exceed max supply")]
+    InvalidMaxPerWallet,
+    #[msg("Invalid allowlist configuration")]
+    InvalidAllowlistConfig,
+    #[msg("Invalid sale phase configuration")]
+    InvalidPhaseConfig,
+    #[msg("Invalid token gate configuration")]
+    InvalidGateConfig,
+    #[msg("Payment mint is not allowed by the protocol config")]
+    MintNotAllowed,
+    #[msg("Payee shares must be non-zero and sum to 10000 basis points")]
+    InvalidPayeeConfig,
+    #[msg("Referral fee exceeds the maximum")]
+    InvalidReferralFee,
+    #[msg("Vesting cliff must not exceed a non-zero duration")]
+    InvalidVestingSchedule,
+    #[msg("Invalid milestone configuration")]
+    InvalidMilestoneConfig,
+    #[msg("Delivery period must be greater than zero")]
+    InvalidDeliveryPeriod,
+    #[msg("Multisig signers must be unique with a threshold between 1 and the signer count")]
+    InvalidMultisigConfig,
 
     // Bid errors
     #[msg("Bid amount is less than current price")]
@@ -29,6 +51,30 @@ pub enum SuperpullProgramError {
     InvalidBidder,
     #[msg("Token account mint does not match auction token mint")]
     InvalidTokenMint,
+    #[msg("Wallet has reached the per-wallet purchase limit")]
+    WalletLimitReached,
+    #[msg("An allowlist proof is required during the presale")]
+    AllowlistProofRequired,
+    #[msg("Allowlist proof does not match the merkle root")]
+    InvalidAllowlistProof,
+    #[msg("Wallet has used its entire allowlist allocation")]
+    AllowlistAllocationExceeded,
+    #[msg("Auction phases account is required")]
+    PhasesAccountRequired,
+    #[msg("No sale phase is currently active")]
+    NoActivePhase,
+    #[msg("Phase supply allocation reached")]
+    PhaseSupplyReached,
+    #[msg("Wallet has reached the purchase limit for this phase")]
+    PhaseWalletLimitReached,
+    #[msg("Token gate accounts are missing")]
+    GateAccountsMissing,
+    #[msg("Bidder does not satisfy the token gate")]
+    GateCheckFailed,
+    #[msg("Gating NFT has already been used for this auction")]
+    GateNftAlreadyUsed,
+    #[msg("Bidder cannot refer themselves")]
+    SelfReferral,
 
     // Withdrawal errors
     #[msg("Unauthorized withdrawal attempt")]
@@ -37,6 +83,8 @@ pub enum SuperpullProgramError {
     NotGraduated,
     #[msg("No funds available to withdraw")]
     NoFundsToWithdraw,
+    #[msg("Payee token accounts are missing")]
+    PayeeAccountsMissing,
     #[msg("Cannot withdraw below rent-exempt balance")]
     InsufficientRentBalance,
     #[msg("Withdrawal amount exceeds available balance")]
@@ -49,6 +97,8 @@ pub enum SuperpullProgramError {
     MinimumItemsNotReached,
     #[msg("Invalid auction state")]
     InvalidAuctionState,
+    #[msg("Phases cannot be changed once bidding has started")]
+    PhasesLocked,
 
     // Account validation errors
     #[msg("Invalid authority provided")]
@@ -57,6 +107,22 @@ pub enum SuperpullProgramError {
     InvalidAccountOwner,
     #[msg("Account is not rent exempt")]
     NotRentExempt,
+    #[msg("Token account owner or mint does not match")]
+    InvalidTokenAccount,
+    #[msg("Account data does not match the expected layout")]
+    InvalidAccountData,
+    #[msg("Account is already on the current layout")]
+    AlreadyMigrated,
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+    #[msg("Treasury cannot be the zero address")]
+    InvalidTreasury,
+
+    // Protocol config errors
+    #[msg("Protocol fee exceeds the maximum")]
+    InvalidProtocolFee,
+    #[msg("Too many allowed payment mints")]
+    TooManyAllowedMints,
 
     // Auction expiration errors
     #[msg("Invalid deadline")]
@@ -64,6 +130,64 @@ pub enum SuperpullProgramError {
     #[msg("Auction expired")]
     AuctionExpired,
 
+    // Milestone errors
+    #[msg("Milestones cannot be changed once bidding has started")]
+    MilestonesLocked,
+    #[msg("Auction milestones account is required")]
+    MilestonesAccountRequired,
+    #[msg("All milestones have been submitted")]
+    NoPendingMilestone,
+    #[msg("The challenge window of the previous milestone is still open")]
+    ChallengeWindowOpen,
+    #[msg("The challenge window of this milestone is closed")]
+    ChallengeWindowClosed,
+    #[msg("Bidder has already vetoed this milestone")]
+    AlreadyVetoed,
+    #[msg("Auction has been halted by its backers")]
+    AuctionHalted,
+
+    // Delivery errors
+    #[msg("Auction has no delivery guarantee")]
+    NoDeliveryGuarantee,
+    #[msg("Delivery has already been confirmed")]
+    DeliveryAlreadyConfirmed,
+    #[msg("Delivery deadline has passed")]
+    DeliveryDeadlinePassed,
+    #[msg("Funds are locked until delivery is confirmed")]
+    DeliveryNotConfirmed,
+
+    // Arbiter errors
+    #[msg("Signer is not the auction's arbiter")]
+    UnauthorizedArbiter,
+    #[msg("Withdrawals are frozen by the arbiter")]
+    WithdrawalsFrozen,
+
+    // Multisig errors
+    #[msg("Action requires an approved multisig proposal")]
+    MultisigRequired,
+    #[msg("Auction has no multisig signer set")]
+    NoMultisig,
+    #[msg("Signer is not part of the auction's signer set")]
+    NotMultisigSigner,
+    #[msg("Signer has already approved this proposal")]
+    ProposalAlreadyApproved,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal has not reached the approval threshold")]
+    InsufficientApprovals,
+    #[msg("Proposal action cannot be executed by this instruction")]
+    InvalidProposalAction,
+
+    // Registry errors
+    #[msg("Auction is not listed in the registry")]
+    AuctionNotRegistered,
+    #[msg("Registry page does not list the auction")]
+    InvalidRegistryPage,
+
+    // Referral errors
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+
     // Refund errors
     #[msg("Cannot refund when auction is graduated or deadline not reached")]
     InvalidRefundAttempt,
@@ -71,4 +195,36 @@ pub enum SuperpullProgramError {
     NoFundsToRefund,
     #[msg("Failed to burn NFT during refund")]
     NftBurnError,
+    #[msg("Refund accounts must be groups of a bid, its open item receipts, the bidder's token account and wallet")]
+    InvalidRefundAccounts,
+
+    // Sell-back errors
+    #[msg("Sell-back spread exceeds the maximum")]
+    InvalidSellBackSpread,
+    #[msg("Sell-backs can only be configured before the first item is sold")]
+    SellBackLocked,
+    #[msg("Sell-backs are not enabled for this auction")]
+    SellBackDisabled,
+    #[msg("Items can only be sold back before the deadline of an auction that has not graduated")]
+    SellBackClosed,
+
+    // Multisig proposal errors
+    #[msg("Arguments do not match the ones approved by the proposal")]
+    ProposalArgsMismatch,
+
+    // Refund errors
+    #[msg("Every open item receipt of the bid must be closed with its refund")]
+    OpenItemReceipts,
+
+    // Referral errors
+    #[msg("Referrer account does not match the item's referrer")]
+    ReferrerMismatch,
+
+    // Refund batch errors
+    #[msg("Refund bounty exceeds the maximum")]
+    InvalidRefundBounty,
+
+    // Auction update errors
+    #[msg("Deadlines can only be extended before the first item is sold")]
+    DeadlineLocked,
 } 
\ No newline at end of file
@@ -1,9 +1,91 @@
 use anchor_lang::prelude::*;
+use crate::state::{
+    AllowlistConfig, ArbiterRuling, AuctionState, GateRule, Payee, ProposalAction,
+    VestingSchedule,
+};
+
+/// Schema version of the event payloads, the first field of every event.
+/// Bumped whenever a payload changes so indexers can tell layouts apart.
+///
+/// - 2: `ItemRefunded` carries the receipt's `price` and `referral_fee`
+pub const EVENT_VERSION: u8 = 2;
+
+/// Seed of the event authority PDA added to instructions by `#[event_cpi]`
+pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+/// Emits events through a self-CPI signed by the event authority, so they
+/// are recorded as inner instructions rather than logs that can be
+/// truncated. Programs built with the `log-events` feature log them with
+/// `emit!` instead, the event authority accounts stay in the instructions
+/// either way
+#[cfg_attr(feature = "log-events", allow(dead_code))]
+pub struct EventEmitter<'info> {
+    authority: AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'info> EventEmitter<'info> {
+    pub fn new(authority: &AccountInfo<'info>, bump: u8) -> Self {
+        Self {
+            authority: authority.clone(),
+            bump,
+        }
+    }
+
+    #[cfg(feature = "log-events")]
+    pub fn emit<E: anchor_lang::Event>(&self, event: E) -> Result<()> {
+        emit!(event);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "log-events"))]
+    pub fn emit<E: anchor_lang::Event>(&self, event: E) -> Result<()> {
+        let data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .into_iter()
+            .chain(event.data())
+            .collect();
+        let ix = anchor_lang::solana_program::instruction::Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(*self.authority.key, true)],
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            std::slice::from_ref(&self.authority),
+            &[&[EVENT_AUTHORITY_SEED, &[self.bump]]],
+        )
+        .map_err(Into::into)
+    }
+
+    /// Emits `AuctionStatusChanged` when the auction's registry status
+    /// differs from `previous_status`
+    pub fn emit_status_change(
+        &self,
+        auction: &Account<AuctionState>,
+        previous_status: u8,
+        clock: &Clock,
+    ) -> Result<()> {
+        let status = auction.registry_status(clock.unix_timestamp);
+        if status == previous_status {
+            return Ok(());
+        }
+        self.emit(AuctionStatusChanged {
+            version: EVENT_VERSION,
+            auction: auction.key(),
+            previous_status,
+            status,
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+        })
+    }
+}
 
 #[event]
 pub struct AuctionInitialized {
+    pub version: u8,
     pub auction: Pubkey,
     pub authority: Pubkey,
+    pub auction_id: u64,
     pub merkle_tree: Pubkey,
     pub token_mint: Pubkey,
     pub collection_mint: Pubkey,
@@ -12,40 +94,423 @@ pub struct AuctionInitialized {
     pub max_supply: u64,
     pub minimum_items: u64,
     pub deadline: i64,
+    pub max_per_wallet: u64,
+    pub allowlist: Option<AllowlistConfig>,
+    pub gate: Option<GateRule>,
+    pub protocol_fee_bps: u16,
+    pub payees: Vec<Payee>,
+    pub dust_payee_index: u8,
+    pub referral_bps: u16,
+    pub vesting: Option<VestingSchedule>,
+    pub delivery_period: Option<i64>,
+    pub arbiter: Option<Pubkey>,
+    pub treasury: Pubkey,
+    pub multisig_signers: Vec<Pubkey>,
+    pub multisig_threshold: u8,
+    pub timestamp: i64,
+    pub slot: u64,
 }
 
 #[event]
 pub struct PriceUpdate {
+    pub version: u8,
     pub auction: Pubkey,
     pub price: u64,
     pub supply: u64,
+    pub timestamp: i64,
+    pub slot: u64,
 }
 
 #[event]
 pub struct BidPlaced {
+    pub version: u8,
     pub auction: Pubkey,
     pub bidder: Pubkey,
     pub amount: u64,
     pub new_supply: u64,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    /// Price of the item, bids buy a single item
+    pub price: u64,
+    /// Serial number of the item in the auction, starting at 1
+    pub item_serial: u64,
+    /// Index of the minted leaf in the merkle tree
+    pub leaf_index: u64,
+    /// Bubblegum asset id of the minted cNFT
+    pub asset_id: Pubkey,
+    /// Wallet that paid for the bid's accounts
+    pub payer: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
 }
 
 #[event]
 pub struct AuctionGraduated {
+    pub version: u8,
     pub auction: Pubkey,
     pub total_items: u64,
     pub total_value_locked: u64,
+    pub graduated_at: i64,
+    pub timestamp: i64,
+    pub slot: u64,
 }
 
 #[event]
 pub struct FundsWithdrawn {
+    pub version: u8,
     pub auction: Pubkey,
     pub authority: Pubkey,
     pub amount: u64,
+    pub protocol_fee: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct WithdrawableBalance {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub total_proceeds: u64,
+    pub vested: u64,
+    pub withdrawn: u64,
+    pub withdrawable: u64,
+    pub remaining: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct PayeePaid {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub payee: Pubkey,
+    pub share_bps: u16,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProtocolFeeCollected {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub treasury: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub treasury: Pubkey,
+    pub protocol_fee_bps: u16,
+    pub allowed_mints: Vec<Pubkey>,
+    pub timestamp: i64,
+    pub slot: u64,
 }
 
 #[event]
 pub struct BidRefunded {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct PhasesConfigured {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub phase_count: u8,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// First bid of a phase. Phases open at their `start_time` whether or not
+/// they get bids, `PhasesConfigured` announces the schedule
+#[event]
+pub struct PhaseFirstBid {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub phase_index: u8,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub base_price: u64,
+    pub price_increment: u64,
+    pub supply: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ReferrerRegistered {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub referrer: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub referrer: Pubkey,
+    pub amount: u64,
+    pub total_rewards: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct MilestonesConfigured {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub milestone_count: u8,
+    pub challenge_period: i64,
+    pub veto_threshold_bps: u16,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct MilestoneSubmitted {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub milestone_index: u8,
+    pub release_bps: u16,
+    pub evidence_hash: [u8; 32],
+    pub challenge_ends_at: i64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct MilestoneVetoed {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub milestone_index: u8,
+    pub bidder: Pubkey,
+    pub weight: u64,
+    pub veto_weight: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct AuctionHalted {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub milestone_index: u8,
+    pub refund_pool: u64,
+    pub refund_basis: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct DeliveryConfirmed {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub authority: Pubkey,
+    pub confirmed_at: i64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ArbiterRuled {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub arbiter: Pubkey,
+    pub ruling_index: u32,
+    pub ruling: ArbiterRuling,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct AuthorityProposed {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TreasuryUpdated {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct AuctionCancelled {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub refund_pool: u64,
+    pub refund_basis: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct AuctionUpdated {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub deadline: i64,
+    pub max_per_wallet: u64,
+    pub treasury: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct AuctionRegistered {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub authority: Pubkey,
+    pub global_index: u64,
+    pub authority_index: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct RegistryStatusUpdated {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub status: u8,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub proposal: Pubkey,
+    pub index: u32,
+    pub proposer: Pubkey,
+    pub action: ProposalAction,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub proposal: Pubkey,
+    pub signer: Pubkey,
+    pub approvals: u8,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub proposal: Pubkey,
+    pub index: u32,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// Refund of a single item, emitted for each cNFT an item-level refund
+/// burns
+#[event]
+pub struct ItemRefunded {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub item_serial: u64,
+    pub asset_id: Pubkey,
+    /// Amount refunded, the price in a failed auction and a share of the
+    /// remaining escrow in a halted one
+    pub amount: u64,
+    /// Price recorded in the item's receipt
+    pub price: u64,
+    pub referral_fee: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// Change of the auction's registry status flags, see `registry_status`
+#[event]
+pub struct AuctionStatusChanged {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub previous_status: u8,
+    pub status: u8,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct SellBackConfigured {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub spread_bps: Option<u16>,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// Item sold back to the curve, its cNFT is burned and the supply lowered
+#[event]
+pub struct ItemSoldBack {
+    pub version: u8,
     pub auction: Pubkey,
     pub bidder: Pubkey,
+    pub item_serial: u64,
+    pub asset_id: Pubkey,
+    /// Price recorded in the item's receipt
+    pub price: u64,
+    /// Referral fee of the item, no longer owed to the referrer
+    pub referral_fee: u64,
+    /// Price the item is sold back at, the spread is taken from it
+    pub sell_price: u64,
+    /// Share of the sell price kept in the escrow
+    pub spread: u64,
+    /// Amount paid to the bidder, the rest of `price` stays in the escrow
     pub amount: u64,
-} 
\ No newline at end of file
+    pub new_supply: u64,
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+/// Item minted to a recipient other than the bidder, emitted after its
+/// `BidPlaced`
+#[event]
+pub struct ItemGifted {
+    pub version: u8,
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub recipient: Pubkey,
+    pub item_serial: u64,
+    pub asset_id: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64,
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// Leaf committing a bidder to their allowlist allocation
+pub fn allowlist_leaf(bidder: &Pubkey, allocation: u64) -> [u8; 32] {
+    keccak::hashv(&[bidder.as_ref(), &allocation.to_le_bytes()]).to_bytes()
+}
+
+/// Verifies a merkle proof built with sorted-pair keccak hashing
+pub fn verify_proof(proof: &[[u8; 32]], root: [u8; 32], leaf: [u8; 32]) -> bool {
+    let computed = proof.iter().fold(leaf, |node, sibling| {
+        if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        }
+    });
+    computed == root
+}
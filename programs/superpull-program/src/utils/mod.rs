@@ -1,2 +1,3 @@
 pub mod errors;
-pub mod events;
\ No newline at end of file
+pub mod events;
+pub mod merkle;
\ No newline at end of file
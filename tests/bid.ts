@@ -188,4 +188,42 @@ describe("Superpull Program - Bid Flow", () => {
     await logBidState(ctx, bidPda, "Final State");
     await logAuctionState(ctx, "Final State");
   });
-}); 
\ No newline at end of file
+
+  it("should reject bids beyond the per-wallet limit", async () => {
+    console.log("\n🧪 TEST: Rejecting bids beyond the per-wallet limit");
+    // Setup fresh context for this test
+    const ctx = await setupAuctionContext();
+
+    // Initialize auction with a limit of 2 items per wallet
+    const basePrice = 1;
+    const maxPerWallet = 2;
+    await initializeAuction(ctx, basePrice, 1, 7, 5, 24 * 60 * 60, maxPerWallet);
+    await logAuctionState(ctx, "After Initialization");
+
+    // Find bid PDA
+    const [bidPda] = findBidPda(ctx.program, ctx.auctionPda, ctx.provider.publicKey);
+
+    // Place bids up to the limit
+    for (let i = 0; i < maxPerWallet; i++) {
+      await placeBid(ctx, basePrice + i);
+      await logBidState(ctx, bidPda, `After Bid ${i + 1}`);
+    }
+
+    const bidState = await ctx.program.account.bidState.fetch(bidPda);
+    assert.ok(bidState.count.eq(new anchor.BN(maxPerWallet)), "Bid count should match the limit");
+
+    // Attempt to exceed the limit (should fail)
+    console.log("\n❌ Attempting to exceed per-wallet limit...");
+    try {
+      await placeBid(ctx, basePrice + maxPerWallet);
+      assert.fail("Should not be able to bid beyond the per-wallet limit");
+    } catch (error) {
+      console.log("✅ Bid correctly rejected beyond the per-wallet limit");
+      console.log("🔍 Error:", error.toString());
+    }
+
+    // Log final states
+    await logBidState(ctx, bidPda, "Final State");
+    await logAuctionState(ctx, "Final State");
+  });
+});
@@ -0,0 +1,125 @@
+import * as anchor from "@coral-xyz/anchor";
+import * as fs from "fs";
+import * as path from "path";
+import { PublicKey } from "@solana/web3.js";
+import {
+  setupTestContext,
+  setupCollection,
+  setupMerkleTree,
+  initializeAuction,
+  placeBid,
+  TestContext,
+  findBidPda,
+  findItemReceiptPda,
+  createAuthorityTokenAccount,
+} from "./helpers";
+
+// Records the indexer fixtures in tests/fixtures from a localnet run. The
+// fixtures are the raw `getTransaction` responses of every transaction that
+// touched the auction and must not be edited by hand, rerun the capture
+// instead:
+//
+//   CAPTURE_FIXTURES=logs anchor test -- --features log-events
+//   CAPTURE_FIXTURES=cpi anchor test
+const CAPTURE = process.env.CAPTURE_FIXTURES;
+const FIXTURES_DIR = path.join(__dirname, "fixtures");
+
+async function captureTransactions(ctx: TestContext, address: PublicKey, fixture: string) {
+  const connection = ctx.provider.connection;
+  // Signatures come newest first, the indexer applies transactions in the
+  // order they landed
+  const signatures = (await connection.getSignaturesForAddress(address, {}, "confirmed")).reverse();
+  const transactions = [];
+  for (const { signature } of signatures) {
+    const response = await (connection as any)._rpcRequest("getTransaction", [
+      signature,
+      { encoding: "json", commitment: "confirmed", maxSupportedTransactionVersion: 0 },
+    ]);
+    if (response.error) {
+      throw new Error(`getTransaction ${signature}: ${response.error.message}`);
+    }
+    transactions.push(response.result);
+  }
+  fs.writeFileSync(path.join(FIXTURES_DIR, fixture), JSON.stringify(transactions, null, 2) + "\n");
+  console.log(`📼 Captured ${transactions.length} transactions to ${fixture}`);
+}
+
+(CAPTURE ? describe : describe.skip)("Superpull Program - Indexer Fixtures", () => {
+  async function setupAuctionContext(): Promise<TestContext> {
+    const ctx = await setupTestContext();
+    await setupCollection(ctx);
+    await setupMerkleTree(ctx);
+    return ctx;
+  }
+
+  async function graduateAndWithdraw(ctx: TestContext) {
+    const minimumItems = 2;
+    await initializeAuction(ctx, 1, 1, 7, minimumItems);
+    const authorityTokenAccount = await createAuthorityTokenAccount(ctx, ctx.auctionCreator);
+    for (let i = 0; i < minimumItems; i++) {
+      await placeBid(ctx, 1 + i);
+    }
+
+    await ctx.program.methods
+      .withdraw(null)
+      .accounts({
+        funds: {
+          auction: ctx.auctionPda,
+          milestones: null,
+          authorityTokenAccount: authorityTokenAccount,
+          treasuryTokenAccount: ctx.treasuryTokenAccount,
+          auctionTokenAccount: ctx.auctionTokenAccount,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        },
+        authority: ctx.auctionCreator.publicKey,
+      })
+      .signers([ctx.auctionCreator])
+      .rpc();
+  }
+
+  async function failAndRefund(ctx: TestContext) {
+    const shortDeadline = 5;
+    await initializeAuction(ctx, 1, 1, 7, 5, shortDeadline);
+    await placeBid(ctx, 1);
+    await new Promise((resolve) => setTimeout(resolve, (shortDeadline + 1) * 1000));
+
+    const [bidPda] = findBidPda(ctx.program, ctx.auctionPda, ctx.provider.publicKey);
+    const receiptPda = findItemReceiptPda(ctx.program, ctx.auctionPda, new anchor.BN(1));
+    await ctx.program.methods
+      .refund()
+      .accounts({
+        auction: ctx.auctionPda,
+        bid: bidPda,
+        bidder: ctx.provider.publicKey,
+        bidderTokenAccount: ctx.bidderTokenAccount,
+        auctionTokenAccount: ctx.auctionTokenAccount,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([{ pubkey: receiptPda, isWritable: true, isSigner: false }])
+      .rpc();
+  }
+
+  if (CAPTURE === "logs") {
+    it("captures a graduated auction with events in the logs", async () => {
+      const ctx = await setupAuctionContext();
+      await graduateAndWithdraw(ctx);
+      await captureTransactions(ctx, ctx.auctionPda, "graduated_auction.json");
+    });
+
+    it("captures a refunded auction with events in the logs", async () => {
+      const ctx = await setupAuctionContext();
+      await failAndRefund(ctx);
+      await captureTransactions(ctx, ctx.auctionPda, "refunded_auction.json");
+    });
+  }
+
+  if (CAPTURE === "cpi") {
+    it("captures a graduated auction with events through the event CPI", async () => {
+      const ctx = await setupAuctionContext();
+      await graduateAndWithdraw(ctx);
+      await captureTransactions(ctx, ctx.auctionPda, "cpi_events.json");
+    });
+  }
+});
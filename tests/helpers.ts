@@ -54,6 +54,7 @@ export const MAX_BUFFER_SIZE = 64;
 export const COLLECTION_NAME = "SuperPull Collection";
 export const COLLECTION_SYMBOL = "SPULL";
 export const COLLECTION_URI = "https://assets.superpull.world/collection.json";
+export const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
 
 // Helper Types
 export interface TestContext {
@@ -69,6 +70,7 @@ export interface TestContext {
   tokenMint: PublicKey;
   bidderTokenAccount: PublicKey;
   auctionTokenAccount: PublicKey;
+  treasuryTokenAccount: PublicKey;
 }
 
 // Setup Functions
@@ -90,10 +92,12 @@ export async function setupTestContext(): Promise<TestContext> {
   console.log("🌳 Generated merkle tree:", merkleTree.publicKey.toString());
   const collectionMint = generateSigner(umi);
 
-  // Calculate auction PDA
-  const [auctionPda] = PublicKey.findProgramAddressSync(
-    [Buffer.from("auction"), auctionCreator.publicKey.toBuffer(), toWeb3JsPublicKey(collectionMint.publicKey).toBuffer()],
-    program.programId
+  // Calculate auction PDA, the creator is fresh so this is their first auction
+  const auctionPda = findAuctionPda(
+    program,
+    auctionCreator.publicKey,
+    toWeb3JsPublicKey(collectionMint.publicKey),
+    new BN(1)
   );
   console.log("🎯 Auction PDA:", auctionPda.toString());
 
@@ -153,6 +157,19 @@ export async function setupTestContext(): Promise<TestContext> {
   );
   console.log("💸 Minted tokens to bidder");
 
+  // Create the protocol config, with the payer as admin and treasury
+  await ensureGlobalConfig(program, payer);
+
+  // Create treasury's token account
+  const treasuryTokenAccount = await createTokenAccount(
+    provider.connection,
+    payer.payer,
+    tokenMint,
+    payer.publicKey,
+    anchor.web3.Keypair.generate()
+  );
+  console.log("🏦 Created treasury token account:", treasuryTokenAccount.toString());
+
   const collectionAuthorityRecordPda = findCollectionAuthorityRecordPda(umi, {
     mint: collectionMint.publicKey,
     collectionAuthority: fromWeb3JsPublicKey(auctionPda),
@@ -171,9 +188,42 @@ export async function setupTestContext(): Promise<TestContext> {
     tokenMint,
     bidderTokenAccount,
     auctionTokenAccount,
+    treasuryTokenAccount,
   };
 }
 
+export async function ensureGlobalConfig(
+  program: Program<SuperpullProgram>,
+  admin: anchor.Wallet,
+  protocolFeeBps: number = 0
+) {
+  const configPda = findConfigPda(program);
+  const existing = await program.account.globalConfig.fetchNullable(configPda);
+  if (existing) {
+    return configPda;
+  }
+
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+
+  await program.methods
+    .initializeConfig(protocolFeeBps, [])
+    .accounts({
+      config: configPda,
+      admin: admin.publicKey,
+      treasury: admin.publicKey,
+      program: program.programId,
+      programData,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([admin.payer])
+    .rpc();
+  console.log("✅ Global config initialized");
+  return configPda;
+}
+
 export async function setupCollection(ctx: TestContext) {
   console.log("🎨 Creating NFT collection...");
   const collectionTx = await createNft(ctx.umi, {
@@ -239,7 +289,20 @@ export async function initializeAuction(
   priceIncrement: number = 1,
   maxSupply: number = 7,
   minimumItems: number = 5,
-  deadlineOffset: number = 24 * 60 * 60 // 24 hours from now
+  deadlineOffset: number = 24 * 60 * 60, // 24 hours from now
+  maxPerWallet: number = 0, // unlimited
+  allowlist: { merkleRoot: number[]; endTime: BN; price: BN | null } | null = null,
+  gate: object | null = null,
+  payees: { wallet: PublicKey; shareBps: number }[] = [],
+  dustPayeeIndex: number = 0,
+  referralBps: number = 0,
+  vesting: { cliff: BN; duration: BN } | null = null,
+  deliveryPeriod: number | null = null,
+  arbiter: PublicKey | null = null,
+  treasury: PublicKey | null = null,
+  multisigSigners: PublicKey[] = [],
+  multisigThreshold: number = 0,
+  authoritySigns: boolean = true
 ) {
   console.log("🎯 Initializing auction...");
 
@@ -248,11 +311,31 @@ export async function initializeAuction(
     priceIncrement: new BN(priceIncrement),
     maxSupply: new BN(maxSupply),
     minimumItems: new BN(minimumItems),
-    deadline: new BN(Math.floor(Date.now() / 1000) + deadlineOffset)
+    deadline: new BN(Math.floor(Date.now() / 1000) + deadlineOffset),
+    maxPerWallet: new BN(maxPerWallet),
+    allowlist,
+    gate,
+    payees,
+    dustPayeeIndex,
+    referralBps,
+    vesting,
+    deliveryPeriod: deliveryPeriod === null ? null : new BN(deliveryPeriod),
+    arbiter,
+    treasury,
+    multisigSigners,
+    multisigThreshold,
   };
 
+  const globalRegistry = findRegistryPda(ctx.program);
+  const authorityRegistry = findRegistryPda(ctx.program, ctx.auctionCreator.publicKey);
   const accounts = {
     auction: ctx.auctionPda,
+    sellerCounter: findSellerCounterPda(ctx.program, ctx.auctionCreator.publicKey),
+    globalRegistry: globalRegistry,
+    globalRegistryPage: await findCurrentRegistryPagePda(ctx.program, globalRegistry),
+    authorityRegistry: authorityRegistry,
+    authorityRegistryPage: await findCurrentRegistryPagePda(ctx.program, authorityRegistry),
+    config: findConfigPda(ctx.program),
     merkleTree: toWeb3JsPublicKey(ctx.merkleTree.publicKey),
     treeConfig: ctx.treeConfigPda,
     treeCreator: ctx.payer.publicKey,
@@ -264,17 +347,46 @@ export async function initializeAuction(
     systemProgram: SystemProgram.programId,
   };
 
-  await ctx.program.methods
+  const method = ctx.program.methods
     .initializeAuction(
       auctionParams.basePrice,
       auctionParams.priceIncrement,
       auctionParams.maxSupply,
       auctionParams.minimumItems,
-      auctionParams.deadline
+      auctionParams.deadline,
+      auctionParams.maxPerWallet,
+      auctionParams.allowlist,
+      auctionParams.gate,
+      auctionParams.payees,
+      auctionParams.dustPayeeIndex,
+      auctionParams.referralBps,
+      auctionParams.vesting,
+      auctionParams.deliveryPeriod,
+      auctionParams.arbiter,
+      auctionParams.treasury,
+      auctionParams.multisigSigners,
+      auctionParams.multisigThreshold
     )
-    .accounts(accounts)
-    .signers([ctx.payer.payer])
-    .rpc({ skipPreflight: true });
+    .accounts(accounts);
+
+  if (authoritySigns) {
+    await method
+      .signers([ctx.payer.payer, ctx.auctionCreator])
+      .rpc({ skipPreflight: true });
+  } else {
+    // Send the instruction with the authority as a plain account
+    const instruction = await method.instruction();
+    for (const key of instruction.keys) {
+      if (key.pubkey.equals(ctx.auctionCreator.publicKey)) {
+        key.isSigner = false;
+      }
+    }
+    await ctx.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(instruction),
+      [ctx.payer.payer],
+      { skipPreflight: true }
+    );
+  }
 
   console.log("✅ Auction initialized successfully");
   return auctionParams;
@@ -282,7 +394,8 @@ export async function initializeAuction(
 
 export async function placeBid(
   ctx: TestContext,
-  bidAmount: number
+  bidAmount: number,
+  allowlistProof: { allocation: BN; proof: number[][] } | null = null
 ) {
   const [bidPda] = PublicKey.findProgramAddressSync(
     [
@@ -292,6 +405,8 @@ export async function placeBid(
     ],
     ctx.program.programId
   );
+  const auctionState = await ctx.program.account.auctionState.fetch(ctx.auctionPda);
+  const receiptPda = findItemReceiptPda(ctx.program, ctx.auctionPda, auctionState.currentSupply.addn(1));
 
   const masterEdition = await fetchMasterEditionFromSeeds(ctx.umi, { mint: ctx.collectionMint.publicKey });
   console.log("👤 Master Edition:", masterEdition);
@@ -303,8 +418,12 @@ export async function placeBid(
   const accounts = {
     auction: ctx.auctionPda,
     bid: bidPda,
+    receipt: receiptPda,
+    phases: null,
+    referrerState: null,
     bidder: ctx.payer.publicKey,
     payer: ctx.payer.publicKey,
+    recipient: null,
     bidderTokenAccount: ctx.bidderTokenAccount,
     auctionTokenAccount: ctx.auctionTokenAccount,
     collectionMint: toWeb3JsPublicKey(ctx.collectionMint.publicKey),
@@ -323,7 +442,7 @@ export async function placeBid(
   };
 
   await ctx.program.methods
-    .placeBid(new BN(bidAmount))
+    .placeBid(new BN(bidAmount), allowlistProof)
     .accounts(accounts)
     .signers([ctx.payer.payer])
     .rpc({ skipPreflight: true });
@@ -345,6 +464,7 @@ export async function logAuctionState(ctx: TestContext, label: string) {
     currentSupply: auctionState.currentSupply.toString(),
     totalValueLocked: auctionState.totalValueLocked.toString(),
     deadline: auctionState.deadline.toString(),
+    maxPerWallet: auctionState.maxPerWallet.toString(),
     isGraduated: auctionState.isGraduated,
     currentPrice: currentPrice.toString(),
   });
@@ -357,6 +477,7 @@ export async function logBidState(ctx: TestContext, bidPda: anchor.web3.PublicKe
       auction: bidState.auction.toString(),
       bidder: bidState.bidder.toString(),
       amount: bidState.amount.toString(),
+      count: bidState.count.toString(),
       bump: bidState.bump
     });
   } catch (error) {
@@ -412,6 +533,88 @@ export function findBubblegumSignerPda(): PublicKey {
   return pda;
 }
 
+export function findConfigPda(program: Program<SuperpullProgram>): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  return pda;
+}
+
+export function findSellerCounterPda(
+  program: Program<SuperpullProgram>,
+  authority: PublicKey
+): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("seller"), authority.toBuffer()],
+    program.programId
+  );
+  return pda;
+}
+
+export function findAuctionPda(
+  program: Program<SuperpullProgram>,
+  authority: PublicKey,
+  collectionMint: PublicKey,
+  auctionId: BN
+): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("auction"),
+      authority.toBuffer(),
+      collectionMint.toBuffer(),
+      auctionId.toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+  return pda;
+}
+
+export function findRegistryPda(
+  program: Program<SuperpullProgram>,
+  authority: PublicKey | null = null
+): PublicKey {
+  const seeds = authority ? [Buffer.from("registry"), authority.toBuffer()] : [Buffer.from("registry")];
+  const [pda] = PublicKey.findProgramAddressSync(seeds, program.programId);
+  return pda;
+}
+
+export function findRegistryPagePda(
+  program: Program<SuperpullProgram>,
+  registry: PublicKey,
+  pageIndex: number
+): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry_page"), registry.toBuffer(), new BN(pageIndex).toArrayLike(Buffer, "le", 4)],
+    program.programId
+  );
+  return pda;
+}
+
+// Page the next auction is appended to, registries list REGISTRY_PAGE_SIZE auctions per page
+export const REGISTRY_PAGE_SIZE = 32;
+
+export async function findCurrentRegistryPagePda(
+  program: Program<SuperpullProgram>,
+  registry: PublicKey
+): Promise<PublicKey> {
+  const account = await program.account.registry.fetchNullable(registry);
+  const auctionCount = account ? account.auctionCount.toNumber() : 0;
+  return findRegistryPagePda(program, registry, Math.floor(auctionCount / REGISTRY_PAGE_SIZE));
+}
+
+export function findItemReceiptPda(
+  program: Program<SuperpullProgram>,
+  auctionPda: PublicKey,
+  serial: BN
+): PublicKey {
+  const [pda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("receipt"), auctionPda.toBuffer(), serial.toArrayLike(Buffer, "le", 8)],
+    program.programId
+  );
+  return pda;
+}
+
 export function findBidPda(
   program: Program<SuperpullProgram>,
   auctionPda: PublicKey,
@@ -450,8 +653,11 @@ export function getCommonAccounts(
   return {
     auction: ctx.auctionPda,
     bid: bidPda,
+    phases: null,
+    referrerState: null,
     bidder: ctx.payer.publicKey,
     payer: ctx.payer.publicKey,
+    recipient: null,
     bidderTokenAccount: ctx.bidderTokenAccount,
     auctionTokenAccount: ctx.auctionTokenAccount,
     collectionMint: toWeb3JsPublicKey(ctx.collectionMint.publicKey),
@@ -0,0 +1,45 @@
+import { assert } from "chai";
+import {
+  setupTestContext,
+  setupCollection,
+  setupMerkleTree,
+  initializeAuction,
+  TestContext,
+  findSellerCounterPda,
+} from "./helpers";
+
+describe("Superpull Program - Initialize Auction", () => {
+  async function setupAuctionContext(): Promise<TestContext> {
+    const ctx = await setupTestContext();
+    await setupCollection(ctx);
+    await setupMerkleTree(ctx);
+    return ctx;
+  }
+
+  it("should reject an auction not signed by its authority", async () => {
+    console.log("\n🧪 TEST: Rejecting an auction not signed by its authority");
+    const ctx = await setupAuctionContext();
+
+    // The payer tries to create an auction in the name of another seller
+    try {
+      await initializeAuction(
+        ctx, 1, 1, 7, 5, 24 * 60 * 60, 0, null, null, [], 0, 0, null, null, null, null, [], 0,
+        false
+      );
+      assert.fail("Should not create an auction without the authority's signature");
+    } catch (error) {
+      console.log("✅ Auction correctly rejected without the authority's signature");
+      console.log("🔍 Error:", error.toString());
+      // AccountNotSigner
+      assert.include(error.toString(), "0xbc2");
+    }
+
+    // The seller's counter did not advance, its next auction id is still free
+    const sellerCounter = await ctx.program.account.sellerCounter.fetchNullable(
+      findSellerCounterPda(ctx.program, ctx.auctionCreator.publicKey)
+    );
+    assert.isNull(sellerCounter, "Seller counter should not be created");
+    const auction = await ctx.program.account.auctionState.fetchNullable(ctx.auctionPda);
+    assert.isNull(auction, "Auction should not be created");
+  });
+});
@@ -12,6 +12,7 @@ import {
   logBidState,
   logTokenBalances,
   findBidPda,
+  findItemReceiptPda,
 } from "./helpers";
 
 describe("Superpull Program - Refund Flow", () => {
@@ -67,9 +68,27 @@ describe("Superpull Program - Refund Flow", () => {
       tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
     };
 
+    // The receipt of the refunded item is closed with the refund
+    const receiptPda = findItemReceiptPda(ctx.program, ctx.auctionPda, new anchor.BN(1));
+    const receipt = await ctx.program.account.itemReceipt.fetch(receiptPda);
+    assert.ok(receipt.price.eq(new anchor.BN(basePrice)), "Receipt should record the price paid");
+
+    // Every open receipt of the bid must be passed
+    try {
+      await ctx.program.methods
+        .refund()
+        .accounts(accounts)
+        .rpc();
+      assert.fail("Should not be able to refund without the bid's open receipts");
+    } catch (error) {
+      assert.include(error.toString(), "OpenItemReceipts");
+    }
+
+    const balanceBefore = (await getAccount(ctx.provider.connection, ctx.bidderTokenAccount)).amount;
     await ctx.program.methods
       .refund()
       .accounts(accounts)
+      .remainingAccounts([{ pubkey: receiptPda, isWritable: true, isSigner: false }])
       .rpc();
 
     await logTokenBalances(ctx, ctx.bidderTokenAccount, "After Refund");
@@ -77,8 +96,15 @@ describe("Superpull Program - Refund Flow", () => {
     await logAuctionState(ctx, "After Refund");
 
     // Verify refund
+    const balanceAfter = (await getAccount(ctx.provider.connection, ctx.bidderTokenAccount)).amount;
+    assert.equal(Number(balanceAfter) - Number(balanceBefore), basePrice, "Bidder should get the price back");
     const finalBidState = await ctx.program.account.bidState.fetch(bidPda);
     assert.ok(finalBidState.amount.eq(new anchor.BN(0)), "Bid amount should be 0 after refund");
+    assert.ok(finalBidState.openReceipts.eq(new anchor.BN(0)), "No receipt should stay open");
+    assert.isNull(
+      await ctx.program.account.itemReceipt.fetchNullable(receiptPda),
+      "Receipt should be closed after refund"
+    );
   });
 
   it("should reject refund when auction is graduated", async () => {
@@ -0,0 +1,54 @@
+use superpull_program::{instructions::withdrawable_amount, state::AuctionState};
+use crate::fixtures::auction_state;
+
+fn graduated_auction() -> AuctionState {
+    AuctionState {
+        total_value_locked: 1_000,
+        is_graduated: true,
+        graduated_at: 100,
+        ..auction_state()
+    }
+}
+
+#[test]
+fn test_frozen_withdrawals_are_locked() {
+    let auction = graduated_auction();
+    assert_eq!(withdrawable_amount(&auction, None, 200).unwrap(), 1_000);
+
+    let auction = AuctionState {
+        withdrawals_frozen: true,
+        ..graduated_auction()
+    };
+    assert_eq!(withdrawable_amount(&auction, None, 200).unwrap(), 0);
+}
+
+#[test]
+fn test_halted_withdrawals_are_locked() {
+    let auction = AuctionState {
+        is_halted: true,
+        ..graduated_auction()
+    };
+    assert_eq!(withdrawable_amount(&auction, None, 200).unwrap(), 0);
+}
+
+#[test]
+fn test_pending_delivery_locks_withdrawals() {
+    let mut auction = AuctionState {
+        delivery_period: Some(50),
+        ..graduated_auction()
+    };
+    assert_eq!(withdrawable_amount(&auction, None, 120).unwrap(), 0);
+
+    // Confirmed by the seller or released by the arbiter
+    auction.delivery_confirmed = true;
+    assert_eq!(withdrawable_amount(&auction, None, 120).unwrap(), 1_000);
+}
+
+#[test]
+fn test_milestones_account_required() {
+    let auction = AuctionState {
+        has_milestones: true,
+        ..graduated_auction()
+    };
+    assert!(withdrawable_amount(&auction, None, 200).is_err());
+}
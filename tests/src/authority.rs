@@ -0,0 +1,65 @@
+use anchor_lang::prelude::Pubkey;
+use superpull_program::state::AuctionState;
+use crate::fixtures::auction_state;
+
+fn auction_address(authority: &Pubkey, collection_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"auction", authority.as_ref(), collection_mint.as_ref()],
+        &superpull_program::ID,
+    )
+}
+
+#[test]
+fn test_signer_seeds_survive_authority_transfer() {
+    let original = Pubkey::new_unique();
+    let collection_mint = Pubkey::new_unique();
+    let (address, bump) = auction_address(&original, &collection_mint);
+
+    let mut auction = AuctionState {
+        authority: original,
+        creator: original,
+        collection_mint,
+        bump,
+        ..auction_state()
+    };
+    auction.authority = Pubkey::new_unique();
+
+    let signer = auction.with_signer_seeds(|seeds| {
+        Pubkey::create_program_address(seeds[0], &superpull_program::ID).unwrap()
+    });
+    assert_eq!(signer, address);
+}
+
+#[test]
+fn test_legacy_auction_seeds_use_authority() {
+    let authority = Pubkey::new_unique();
+    let collection_mint = Pubkey::new_unique();
+    let (address, bump) = auction_address(&authority, &collection_mint);
+
+    // Auctions created before the creator field was added
+    let auction = AuctionState {
+        authority,
+        collection_mint,
+        bump,
+        ..auction_state()
+    };
+    assert_eq!(auction.seed_authority(), &authority);
+    let signer = auction.with_signer_seeds(|seeds| {
+        Pubkey::create_program_address(seeds[0], &superpull_program::ID).unwrap()
+    });
+    assert_eq!(signer, address);
+}
+
+#[test]
+fn test_payout_wallet_defaults_to_authority() {
+    let authority = Pubkey::new_unique();
+    let mut auction = AuctionState {
+        authority,
+        ..auction_state()
+    };
+    assert_eq!(auction.payout_wallet(), authority);
+
+    let treasury = Pubkey::new_unique();
+    auction.treasury = treasury;
+    assert_eq!(auction.payout_wallet(), treasury);
+}
@@ -0,0 +1,310 @@
+use anchor_lang::{prelude::Pubkey, Discriminator};
+use superpull_client::{instructions, pda, quote};
+use superpull_program::{
+    state::{AllowlistConfig, AuctionPhases, AuctionState, Payee, Phase, PhaseArgs, RegistryPosition},
+    instructions::ItemLeaf,
+    utils::errors::SuperpullProgramError,
+};
+use crate::fixtures::auction_state;
+
+fn auction(authority: Pubkey, collection_mint: Pubkey, auction_id: u64) -> (Pubkey, AuctionState) {
+    let (address, bump) = pda::find_auction(&authority, &collection_mint, auction_id);
+    let state = AuctionState {
+        authority,
+        creator: authority,
+        collection_mint,
+        merkle_tree: Pubkey::new_unique(),
+        token_mint: Pubkey::new_unique(),
+        base_price: 100,
+        price_increment: 10,
+        max_supply: 10,
+        minimum_items: 5,
+        deadline: 1_000,
+        referral_bps: 1_000,
+        bump,
+        auction_id,
+        ..auction_state()
+    };
+    (address, state)
+}
+
+#[test]
+fn test_auction_pda_matches_program_signer() {
+    for auction_id in [0, 1, 7] {
+        let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), auction_id);
+        let signer = state.with_signer_seeds(|seeds| {
+            Pubkey::create_program_address(seeds[0], &superpull_program::ID).unwrap()
+        });
+        assert_eq!(signer, address);
+    }
+}
+
+#[test]
+fn test_place_bid_fills_bubblegum_accounts() {
+    let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let bidder = Pubkey::new_unique();
+    let ix = instructions::place_bid(&address, &state, &bidder, &bidder, None, 100, None, None, None);
+
+    assert_eq!(ix.program_id, superpull_program::ID);
+    assert_eq!(&ix.data[..8], superpull_program::instruction::PlaceBid::DISCRIMINATOR);
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(keys.len(), 25);
+    assert_eq!(keys[1], pda::find_bid(&address, &bidder).0);
+    // The receipt of the first item has serial 1
+    assert_eq!(keys[2], pda::find_item_receipt(&address, 1).0);
+    // Unset optional accounts are passed as the program id
+    assert_eq!(keys[3], superpull_program::ID);
+    assert_eq!(keys[4], superpull_program::ID);
+    assert_eq!(keys[7], superpull_program::ID);
+    assert_eq!(keys[14], mpl_bubblegum::accounts::TreeConfig::find_pda(&state.merkle_tree).0);
+    assert_eq!(keys[15], address);
+    assert_eq!(keys[16], pda::find_bubblegum_signer().0);
+    assert_eq!(keys[18], mpl_bubblegum::programs::SPL_ACCOUNT_COMPRESSION_ID);
+    assert_eq!(keys[19], mpl_bubblegum::programs::SPL_NOOP_ID);
+    assert_eq!(keys[20], mpl_bubblegum::ID);
+    assert_eq!(keys[23], pda::find_event_authority().0);
+    assert_eq!(keys[24], superpull_program::ID);
+
+    // Later bids pay for the receipt of the next serial
+    let mut state = state;
+    state.current_supply = 4;
+    let ix = instructions::place_bid(&address, &state, &bidder, &bidder, None, 140, None, None, None);
+    assert_eq!(ix.accounts[2].pubkey, pda::find_item_receipt(&address, 5).0);
+}
+
+#[test]
+fn test_gift_bid_keeps_bid_with_bidder() {
+    let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let bidder = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+    let ix = instructions::place_bid(&address, &state, &bidder, &bidder, Some(&recipient), 100, None, None, None);
+
+    // The bid and the token account are the bidder's, only the item goes
+    // to the recipient
+    assert_eq!(ix.accounts[1].pubkey, pda::find_bid(&address, &bidder).0);
+    assert_eq!(ix.accounts[7].pubkey, recipient);
+    assert!(!ix.accounts[7].is_signer && !ix.accounts[7].is_writable);
+    assert_eq!(ix.accounts[8].pubkey, pda::token_account(&bidder, &state.token_mint));
+}
+
+#[test]
+fn test_refund_pays_bidder_token_account() {
+    let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let bidder = Pubkey::new_unique();
+    let receipts = [pda::find_item_receipt(&address, 1).0, pda::find_item_receipt(&address, 3).0];
+    let ix = instructions::refund(&address, &state, &bidder, &receipts);
+
+    assert_eq!(ix.accounts.len(), 11);
+    assert_eq!(ix.accounts[1].pubkey, pda::find_bid(&address, &bidder).0);
+    assert_eq!(ix.accounts[3].pubkey, pda::token_account(&bidder, &state.token_mint));
+    assert_eq!(ix.accounts[7].pubkey, pda::find_event_authority().0);
+    // The bid's open receipts follow the event CPI accounts, to be closed
+    assert_eq!(ix.accounts[9].pubkey, receipts[0]);
+    assert_eq!(ix.accounts[10].pubkey, receipts[1]);
+    assert!(ix.accounts[10].is_writable);
+}
+
+#[test]
+fn test_withdraw_appends_payee_accounts() {
+    let (address, mut state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    state.payees = vec![
+        Payee { wallet: Pubkey::new_unique(), share_bps: 5_000 },
+        Payee { wallet: Pubkey::new_unique(), share_bps: 5_000 },
+    ];
+    let treasury = Pubkey::new_unique();
+    let ix = instructions::withdraw(&address, &state, &treasury, &state.authority, None);
+
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(keys.len(), 14);
+    // Without a payout wallet account the split goes to the payees
+    assert_eq!(keys[3], superpull_program::ID);
+    // The authority signs after the withdraw accounts, payee accounts follow
+    assert_eq!(keys[9], pda::find_event_authority().0);
+    assert_eq!(keys[11], state.authority);
+    assert!(ix.accounts[11].is_signer);
+    assert_eq!(keys[12], pda::token_account(&state.payees[0].wallet, &state.token_mint));
+    assert_eq!(keys[13], pda::token_account(&state.payees[1].wallet, &state.token_mint));
+}
+
+#[test]
+fn test_execute_withdraw_proposal_is_signed_by_payer_only() {
+    let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let payer = Pubkey::new_unique();
+    let ix = instructions::execute_withdraw_proposal(&address, &state, 0, &Pubkey::new_unique(), &payer);
+
+    let signers: Vec<Pubkey> = ix.accounts.iter().filter(|meta| meta.is_signer).map(|meta| meta.pubkey).collect();
+    assert_eq!(signers, vec![payer]);
+}
+
+#[test]
+fn test_refund_item_appends_proof() {
+    let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let bidder = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let leaf = ItemLeaf {
+        root: [1; 32],
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+    };
+    let proof = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let referrer = Pubkey::new_unique();
+    let ix = instructions::refund_item(&address, &state, &bidder, &owner, 2, Some(&referrer), leaf, &proof);
+
+    assert_eq!(&ix.data[..8], superpull_program::instruction::RefundItem::DISCRIMINATOR);
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(keys.len(), 19);
+    assert_eq!(keys[2], pda::find_item_receipt(&address, 2).0);
+    // The referrer's totals drop with the refund
+    assert_eq!(keys[3], pda::find_referrer(&address, &referrer).0);
+    assert!(ix.accounts[3].is_writable);
+    // The owner of a gifted item signs, the bidder who paid is refunded
+    assert!(!ix.accounts[4].is_signer);
+    assert!(ix.accounts[5].is_signer);
+    assert_eq!(keys[5], owner);
+    assert_eq!(keys[6], pda::token_account(&bidder, &state.token_mint));
+    assert_eq!(keys[8], state.merkle_tree);
+    assert_eq!(keys[12], mpl_bubblegum::ID);
+    // Proof nodes follow the event CPI accounts and are read only
+    assert_eq!(keys[17..], proof);
+    assert!(ix.accounts[17..].iter().all(|meta| !meta.is_writable));
+}
+
+#[test]
+fn test_sell_back_pays_bidder_token_account_only() {
+    let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let bidder = Pubkey::new_unique();
+    let leaf = ItemLeaf {
+        root: [1; 32],
+        data_hash: [2; 32],
+        creator_hash: [3; 32],
+    };
+    let proof = [Pubkey::new_unique()];
+    let ix = instructions::sell_back(&address, &state, &bidder, 3, None, leaf, &proof);
+
+    assert_eq!(&ix.data[..8], superpull_program::instruction::SellBack::DISCRIMINATOR);
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(keys.len(), 17);
+    assert_eq!(keys[2], pda::find_item_receipt(&address, 3).0);
+    // Items without a referrer skip the referrer account
+    assert_eq!(keys[3], superpull_program::ID);
+    // The spread stays in the escrow, no payout wallet account is passed
+    assert_eq!(keys[5], pda::token_account(&bidder, &state.token_mint));
+    assert_eq!(keys[6], pda::token_account(&address, &state.token_mint));
+    assert_eq!(keys[16], proof[0]);
+}
+
+#[test]
+fn test_refund_batch_appends_bid_receipts_token_account_and_bidder_groups() {
+    let (address, state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    let cranker = Pubkey::new_unique();
+    let receipts = vec![pda::find_item_receipt(&address, 1).0, pda::find_item_receipt(&address, 2).0];
+    let bids = [(Pubkey::new_unique(), receipts.clone()), (Pubkey::new_unique(), Vec::new())];
+    let ix = instructions::refund_batch(&address, &state, &cranker, &bids, 5_000);
+
+    assert_eq!(&ix.data[..8], superpull_program::instruction::RefundBatch::DISCRIMINATOR);
+    assert_eq!(ix.accounts.len(), 14);
+    assert!(ix.accounts[1].is_signer);
+    assert_eq!(ix.accounts[3].pubkey, anchor_spl::token::ID);
+    let keys: Vec<Pubkey> = ix.accounts[6..].iter().map(|meta| meta.pubkey).collect();
+    let (first, second) = (&bids[0].0, &bids[1].0);
+    assert_eq!(
+        keys,
+        vec![
+            pda::find_bid(&address, first).0,
+            receipts[0],
+            receipts[1],
+            pda::token_account(first, &state.token_mint),
+            *first,
+            pda::find_bid(&address, second).0,
+            pda::token_account(second, &state.token_mint),
+            *second,
+        ]
+    );
+    assert!(ix.accounts[6..].iter().all(|meta| meta.is_writable && !meta.is_signer));
+}
+
+#[test]
+fn test_sync_registry_pairs_pages_with_their_registry() {
+    let (address, mut state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    state.registry_position = Some(RegistryPosition {
+        global_index: 40,
+        authority_index: 3,
+    });
+    // The authority registry stays the creator's after a transfer
+    let creator = state.creator;
+    state.authority = Pubkey::new_unique();
+    let ix = instructions::sync_registry(&address, &state).unwrap();
+
+    let global_registry = pda::find_registry(None).0;
+    let authority_registry = pda::find_registry(Some(&creator)).0;
+    assert_eq!(ix.accounts[1].pubkey, global_registry);
+    assert_eq!(ix.accounts[2].pubkey, pda::find_registry_page(&global_registry, 1).0);
+    assert_eq!(ix.accounts[3].pubkey, authority_registry);
+    assert_eq!(ix.accounts[4].pubkey, pda::find_registry_page(&authority_registry, 0).0);
+}
+
+#[test]
+fn test_quote_bid_matches_curve() {
+    let (_, mut state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    state.current_supply = 3;
+
+    let quote = quote::quote_bid(&state, None, 500, true).unwrap();
+    assert_eq!(quote.price, state.current_price().unwrap());
+    assert_eq!(quote.price, 130);
+    assert_eq!(quote.referral_fee, 13);
+    assert!(!quote.allowlist_only);
+
+    assert_eq!(quote::quote_items(&state, 3).unwrap(), 130 + 140 + 150);
+    assert!(quote::quote_items(&state, 8).is_err());
+}
+
+#[test]
+fn test_quote_bid_uses_allowlist_and_phase_prices() {
+    let (_, mut state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    state.allowlist = Some(AllowlistConfig {
+        merkle_root: [0; 32],
+        end_time: 200,
+        price: Some(50),
+    });
+    let quote = quote::quote_bid(&state, None, 100, false).unwrap();
+    assert_eq!(quote.price, 50);
+    assert!(quote.allowlist_only);
+
+    state.allowlist = None;
+    state.has_phases = true;
+    assert!(quote::quote_bid(&state, None, 100, false).is_err());
+
+    let mut phase = Phase::from(PhaseArgs {
+        start_time: 0,
+        end_time: 500,
+        base_price: 20,
+        price_increment: 5,
+        max_per_wallet: 0,
+        supply: 4,
+        merkle_root: None,
+    });
+    phase.minted = 2;
+    let phases = AuctionPhases {
+        auction: Pubkey::new_unique(),
+        phases: vec![phase],
+        bump: 255,
+    };
+    let quote = quote::quote_bid(&state, Some(&phases), 100, false).unwrap();
+    assert_eq!(quote.price, 30);
+    assert_eq!(quote.phase_index, Some(0));
+}
+
+#[test]
+fn test_quote_bid_rejects_closed_auction() {
+    let (_, mut state) = auction(Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    assert_eq!(
+        quote::quote_bid(&state, None, 1_001, false).unwrap_err(),
+        SuperpullProgramError::AuctionExpired.into()
+    );
+
+    state.is_halted = true;
+    assert_eq!(
+        quote::quote_bid(&state, None, 500, false).unwrap_err(),
+        SuperpullProgramError::AuctionHalted.into()
+    );
+}
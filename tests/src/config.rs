@@ -0,0 +1,27 @@
+use anchor_lang::prelude::Pubkey;
+use superpull_program::state::{fee_amount, GlobalConfig};
+
+#[test]
+fn test_fee_amount_rounds_down() {
+    assert_eq!(fee_amount(1_000, 250), Some(25));
+    assert_eq!(fee_amount(999, 250), Some(24));
+    assert_eq!(fee_amount(1_000, 0), Some(0));
+    assert_eq!(fee_amount(u64::MAX, 10_000), Some(u64::MAX));
+}
+
+#[test]
+fn test_empty_allow_list_accepts_any_mint() {
+    let allowed = Pubkey::new_unique();
+    let mut config = GlobalConfig {
+        admin: Pubkey::new_unique(),
+        treasury: Pubkey::new_unique(),
+        protocol_fee_bps: 100,
+        allowed_mints: vec![],
+        bump: 255,
+    };
+    assert!(config.is_mint_allowed(&Pubkey::new_unique()));
+
+    config.allowed_mints = vec![allowed];
+    assert!(config.is_mint_allowed(&allowed));
+    assert!(!config.is_mint_allowed(&Pubkey::new_unique()));
+}
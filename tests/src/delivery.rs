@@ -0,0 +1,45 @@
+use superpull_program::state::AuctionState;
+use crate::fixtures::auction_state;
+
+fn graduated_auction(delivery_period: Option<i64>) -> AuctionState {
+    AuctionState {
+        total_value_locked: 1_000,
+        is_graduated: true,
+        graduated_at: 100,
+        delivery_period,
+        ..auction_state()
+    }
+}
+
+#[test]
+fn test_delivery_deadline() {
+    let auction = graduated_auction(Some(50));
+    assert!(auction.is_delivery_pending());
+    assert_eq!(auction.delivery_deadline(), Some(150));
+    assert!(!auction.is_delivery_failed(150));
+    assert!(auction.is_delivery_failed(151));
+
+    let auction = AuctionState {
+        is_graduated: false,
+        ..graduated_auction(Some(50))
+    };
+    assert_eq!(auction.delivery_deadline(), None);
+    assert!(!auction.is_delivery_failed(1_000));
+}
+
+#[test]
+fn test_confirmed_delivery_never_fails() {
+    let auction = AuctionState {
+        delivery_confirmed: true,
+        ..graduated_auction(Some(50))
+    };
+    assert!(!auction.is_delivery_pending());
+    assert!(!auction.is_delivery_failed(1_000));
+}
+
+#[test]
+fn test_no_delivery_guarantee() {
+    let auction = graduated_auction(None);
+    assert!(!auction.is_delivery_pending());
+    assert!(!auction.is_delivery_failed(1_000));
+}
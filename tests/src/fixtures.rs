@@ -0,0 +1,136 @@
+use anchor_lang::{
+    prelude::{AccountInfo, Pubkey},
+    solana_program::{program_option::COption, program_pack::Pack},
+};
+use anchor_spl::token::spl_token;
+use superpull_program::state::{AuctionState, BidState, ReferrerState};
+
+/// Auction with every field zeroed or unset, tests override the fields they
+/// exercise
+pub fn auction_state() -> AuctionState {
+    AuctionState {
+        authority: Pubkey::default(),
+        merkle_tree: Pubkey::default(),
+        token_mint: Pubkey::default(),
+        collection_mint: Pubkey::default(),
+        base_price: 0,
+        price_increment: 0,
+        current_supply: 0,
+        max_supply: 0,
+        total_value_locked: 0,
+        minimum_items: 0,
+        deadline: 0,
+        is_graduated: false,
+        bump: 0,
+        max_per_wallet: 0,
+        allowlist: None,
+        has_phases: false,
+        gate: None,
+        protocol_fee_bps: 0,
+        payees: Vec::new(),
+        dust_payee_index: 0,
+        referral_bps: 0,
+        referral_fees_owed: 0,
+        vesting: None,
+        graduated_at: 0,
+        total_withdrawn: 0,
+        has_milestones: false,
+        is_halted: false,
+        refund_pool: 0,
+        refund_basis: 0,
+        delivery_period: None,
+        delivery_confirmed: false,
+        arbiter: None,
+        withdrawals_frozen: false,
+        ruling_count: 0,
+        creator: Pubkey::default(),
+        pending_authority: None,
+        treasury: Pubkey::default(),
+        multisig_signers: Vec::new(),
+        multisig_threshold: 0,
+        proposal_count: 0,
+        auction_id: 0,
+        registry_position: None,
+        sell_back_spread_bps: None,
+        last_item_serial: 0,
+    }
+}
+
+/// Bid with every field zeroed
+pub fn bid_state() -> BidState {
+    BidState {
+        auction: Pubkey::default(),
+        bidder: Pubkey::default(),
+        amount: 0,
+        bump: 0,
+        count: 0,
+        allowlist_used: 0,
+        phase_index: 0,
+        phase_count: 0,
+        referral_fees: 0,
+        vetoed_milestones: 0,
+        open_receipts: 0,
+    }
+}
+
+/// Referrer with every total zeroed
+pub fn referrer_state() -> ReferrerState {
+    ReferrerState {
+        auction: Pubkey::default(),
+        referrer: Pubkey::default(),
+        referral_count: 0,
+        total_volume: 0,
+        total_rewards: 0,
+        claimed_rewards: 0,
+        bump: 0,
+    }
+}
+
+/// Owner and data of an account passed to instruction checks
+pub struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key,
+            owner,
+            lamports: 1_000_000,
+            data,
+        }
+    }
+
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            false,
+            true,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+/// Data of an initialized SPL token account
+pub fn token_account_data(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: spl_token::state::AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    data
+}
@@ -0,0 +1,243 @@
+use anchor_lang::{
+    prelude::{AccountInfo, Pubkey},
+    AccountSerialize, AnchorSerialize,
+};
+use anchor_spl::{
+    metadata::mpl_token_metadata::{
+        accounts::Metadata,
+        types::{Collection, Key},
+    },
+    token::spl_token,
+};
+use superpull_program::{
+    instructions::check_gate,
+    state::{GateMarker, GateRule},
+    utils::errors::SuperpullProgramError,
+};
+use crate::fixtures::{token_account_data, TestAccount};
+
+fn token_account(owner: &Pubkey, mint: &Pubkey, amount: u64) -> TestAccount {
+    TestAccount::new(Pubkey::new_unique(), spl_token::ID, token_account_data(owner, mint, amount))
+}
+
+fn metadata(nft_mint: &Pubkey, collection: Option<Collection>) -> TestAccount {
+    let metadata = Metadata {
+        key: Key::MetadataV1,
+        update_authority: Pubkey::new_unique(),
+        mint: *nft_mint,
+        name: "Gate".to_string(),
+        symbol: "GATE".to_string(),
+        uri: String::new(),
+        seller_fee_basis_points: 0,
+        creators: None,
+        primary_sale_happened: false,
+        is_mutable: false,
+        edition_nonce: None,
+        token_standard: None,
+        collection,
+        uses: None,
+        collection_details: None,
+        programmable_config: None,
+    };
+    TestAccount::new(
+        Metadata::find_pda(nft_mint).0,
+        anchor_spl::metadata::ID,
+        metadata.try_to_vec().unwrap(),
+    )
+}
+
+fn run_gate(
+    gate: GateRule,
+    auction: &Pubkey,
+    bidder: &Pubkey,
+    accounts: &mut [TestAccount],
+) -> anchor_lang::Result<()> {
+    let mut payer = TestAccount::new(*bidder, Pubkey::default(), Vec::new());
+    let mut system_program = TestAccount::new(Pubkey::default(), Pubkey::default(), Vec::new());
+    let remaining: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+    check_gate(
+        gate,
+        auction,
+        bidder,
+        &remaining,
+        &payer.info(),
+        &system_program.info(),
+    )
+}
+
+#[test]
+fn test_token_balance_gate_threshold() {
+    let auction = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let gate = GateRule::TokenBalance {
+        mint,
+        min_amount: 10,
+    };
+
+    assert!(run_gate(
+        gate,
+        &auction,
+        &bidder,
+        &mut [token_account(&bidder, &mint, 10)]
+    )
+    .is_ok());
+    assert_eq!(
+        run_gate(
+            gate,
+            &auction,
+            &bidder,
+            &mut [token_account(&bidder, &mint, 9)]
+        )
+        .unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+
+    // The balance must be of the gate mint and held by the bidder
+    let other_mint = Pubkey::new_unique();
+    assert_eq!(
+        run_gate(
+            gate,
+            &auction,
+            &bidder,
+            &mut [token_account(&bidder, &other_mint, 10)]
+        )
+        .unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+    let other_owner = Pubkey::new_unique();
+    assert_eq!(
+        run_gate(
+            gate,
+            &auction,
+            &bidder,
+            &mut [token_account(&other_owner, &mint, 10)]
+        )
+        .unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut []).unwrap_err(),
+        SuperpullProgramError::GateAccountsMissing.into()
+    );
+}
+
+#[test]
+fn test_collection_gate_requires_verified_collection() {
+    let auction = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+    let collection_mint = Pubkey::new_unique();
+    let gate = GateRule::Collection {
+        collection_mint,
+        one_use_per_nft: false,
+    };
+    let collection = |key, verified| Some(Collection { verified, key });
+
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 1),
+        metadata(&nft_mint, collection(collection_mint, true)),
+    ];
+    assert!(run_gate(gate, &auction, &bidder, &mut accounts).is_ok());
+
+    // Wrong collection
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 1),
+        metadata(&nft_mint, collection(Pubkey::new_unique(), true)),
+    ];
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut accounts).unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+
+    // Unverified collection
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 1),
+        metadata(&nft_mint, collection(collection_mint, false)),
+    ];
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut accounts).unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+
+    // Metadata of another NFT than the one held
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 1),
+        metadata(&Pubkey::new_unique(), collection(collection_mint, true)),
+    ];
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut accounts).unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+
+    // The NFT must still be held
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 0),
+        metadata(&nft_mint, collection(collection_mint, true)),
+    ];
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut accounts).unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+}
+
+#[test]
+fn test_one_use_gate_rejects_used_nft() {
+    let auction = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+    let nft_mint = Pubkey::new_unique();
+    let collection_mint = Pubkey::new_unique();
+    let gate = GateRule::Collection {
+        collection_mint,
+        one_use_per_nft: true,
+    };
+    let collection = Some(Collection {
+        verified: true,
+        key: collection_mint,
+    });
+
+    let (marker_address, bump) = Pubkey::find_program_address(
+        &[b"gate_marker", auction.as_ref(), nft_mint.as_ref()],
+        &superpull_program::ID,
+    );
+    let mut used_marker = Vec::new();
+    GateMarker {
+        auction,
+        nft_mint,
+        bidder: Pubkey::new_unique(),
+        bump,
+    }
+    .try_serialize(&mut used_marker)
+    .unwrap();
+
+    // The marker already exists, the NFT was used by an earlier bid
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 1),
+        metadata(&nft_mint, collection.clone()),
+        TestAccount::new(marker_address, superpull_program::ID, used_marker),
+    ];
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut accounts).unwrap_err(),
+        SuperpullProgramError::GateNftAlreadyUsed.into()
+    );
+
+    // The marker must be the PDA of the held NFT
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 1),
+        metadata(&nft_mint, collection.clone()),
+        TestAccount::new(Pubkey::new_unique(), Pubkey::default(), Vec::new()),
+    ];
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut accounts).unwrap_err(),
+        SuperpullProgramError::GateCheckFailed.into()
+    );
+
+    let mut accounts = [
+        token_account(&bidder, &nft_mint, 1),
+        metadata(&nft_mint, collection),
+    ];
+    assert_eq!(
+        run_gate(gate, &auction, &bidder, &mut accounts).unwrap_err(),
+        SuperpullProgramError::GateAccountsMissing.into()
+    );
+}
@@ -0,0 +1,517 @@
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey, Event};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::Value;
+use superpull_indexer::{
+    decode_inner_instructions, decode_logs, decode_program_logs, IndexedTransaction,
+    InnerInstruction, Projection, SuperpullEvent,
+};
+use superpull_program::{
+    state::{REGISTRY_STATUS_ACTIVE, REGISTRY_STATUS_GRADUATED, REGISTRY_STATUS_HALTED},
+    utils::events::{
+        AuctionStatusChanged, BidRefunded, ItemRefunded, ItemSoldBack, PriceUpdate, EVENT_AUTHORITY_SEED,
+        EVENT_VERSION,
+    },
+};
+
+const GRADUATED_AUCTION: &str = include_str!("../fixtures/graduated_auction.json");
+const REFUNDED_AUCTION: &str = include_str!("../fixtures/refunded_auction.json");
+const CPI_EVENTS: &str = include_str!("../fixtures/cpi_events.json");
+/// The same transactions as recorded before `EVENT_VERSION` 2
+const V1_GRADUATED_AUCTION: &str = include_str!("../fixtures/v1/graduated_auction.json");
+const V1_REFUNDED_AUCTION: &str = include_str!("../fixtures/v1/refunded_auction.json");
+const V1_CPI_EVENTS: &str = include_str!("../fixtures/v1/cpi_events.json");
+/// Version 1 `ItemRefunded` of serial 1 in the refunded auction, without the
+/// receipt's price and referral fee
+const V1_ITEM_REFUNDED: &str = "ySH9ce4K/CgB3epXLRVNfDuLAuEsHYfjMNrv7o3QxD3owNZB3IOyuGsyHPpa3RheiJOl/YgBPsTX4SLe1GNUyt/1DZVjledbYAEAAAAAAAAAsLLyeGN8xvCVM7G6doVnvndPPUHHj9bsiVcRWFMytJtAQg8AAAAAAAB452gAAAAAWnXEEAAAAAA=";
+
+/// Recorded `getTransaction` responses, with the inner instructions
+/// resolved against the static and loaded account keys
+fn transactions(fixture: &str) -> Vec<IndexedTransaction> {
+    let transactions: Vec<Value> = serde_json::from_str(fixture).unwrap();
+    transactions
+        .iter()
+        .map(|transaction| {
+            let meta = &transaction["meta"];
+            let keys: Vec<Pubkey> = transaction["transaction"]["message"]["accountKeys"]
+                .as_array()
+                .into_iter()
+                .chain(meta["loadedAddresses"]["writable"].as_array())
+                .chain(meta["loadedAddresses"]["readonly"].as_array())
+                .flatten()
+                .map(|key| key.as_str().unwrap().parse().unwrap())
+                .collect();
+            let inner_instructions = meta["innerInstructions"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .flat_map(|inner| inner["instructions"].as_array().unwrap())
+                .map(|instruction| InnerInstruction {
+                    program_id: keys[instruction["programIdIndex"].as_u64().unwrap() as usize],
+                    accounts: instruction["accounts"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|index| keys[index.as_u64().unwrap() as usize])
+                        .collect(),
+                    data: bs58::decode(instruction["data"].as_str().unwrap())
+                        .into_vec()
+                        .unwrap(),
+                })
+                .collect();
+
+            IndexedTransaction {
+                slot: transaction["slot"].as_u64().unwrap(),
+                succeeded: meta["err"].is_null(),
+                log_messages: meta["logMessages"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|log| log.as_str().unwrap().to_string())
+                    .collect(),
+                inner_instructions,
+            }
+        })
+        .collect()
+}
+
+fn key(address: &str) -> Pubkey {
+    address.parse().unwrap()
+}
+
+fn program_data<E: Event>(event: E) -> String {
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+fn event_cpi<E: Event>(event_authority: Pubkey, event: E) -> InnerInstruction {
+    InnerInstruction {
+        program_id: superpull_program::ID,
+        accounts: vec![event_authority],
+        data: EVENT_IX_TAG_LE.into_iter().chain(event.data()).collect(),
+    }
+}
+
+fn price_update(auction: Pubkey, price: u64, supply: u64) -> PriceUpdate {
+    PriceUpdate {
+        version: EVENT_VERSION,
+        auction,
+        price,
+        supply,
+        timestamp: 1_760_000_000,
+        slot: 42,
+    }
+}
+
+fn event_names(fixture: &str) -> Vec<Vec<&'static str>> {
+    transactions(fixture)
+        .iter()
+        .map(|transaction| transaction.events().iter().map(SuperpullEvent::name).collect())
+        .collect()
+}
+
+#[test]
+fn test_decode_recorded_transactions() {
+    assert_eq!(
+        event_names(GRADUATED_AUCTION),
+        vec![
+            vec!["AuctionInitialized", "AuctionRegistered"],
+            vec!["BidPlaced"],
+            // The bid failed in the Bubblegum mint after graduating the
+            // auction, the rolled back graduation must not be indexed
+            vec![],
+            vec!["AuctionGraduated", "BidPlaced", "AuctionStatusChanged"],
+            vec!["PriceUpdate"],
+            vec!["ProtocolFeeCollected", "FundsWithdrawn"],
+        ]
+    );
+}
+
+#[test]
+fn test_decode_recorded_event_cpis() {
+    assert_eq!(
+        event_names(CPI_EVENTS),
+        vec![
+            vec!["AuctionInitialized", "AuctionRegistered"],
+            vec![],
+            vec!["AuctionGraduated", "BidPlaced", "AuctionStatusChanged"],
+            vec!["ProtocolFeeCollected", "FundsWithdrawn"],
+        ]
+    );
+
+    // Events recorded as inner instructions leave nothing in the logs
+    for transaction in transactions(CPI_EVENTS) {
+        assert!(decode_logs(&transaction.log_messages).is_empty());
+    }
+}
+
+#[test]
+fn test_project_graduated_auction() {
+    let mut projection = Projection::new();
+    for transaction in transactions(GRADUATED_AUCTION) {
+        projection.apply_transaction(&transaction);
+    }
+
+    let auction_address = key("9nYCsGQnQhFoXbmEVm7Cdp9xqcVrJLfsK3Uzj7cS4ah3");
+    let auction = projection.auction(&auction_address).unwrap();
+    assert_eq!(auction.authority, key("7xKqn5aC2mvtf9b4HmQyqGZVS1QJpZv8JUpvF7mJkR2Z"));
+    assert_eq!(auction.auction_id, 1);
+    assert_eq!(auction.current_supply, 2);
+    assert_eq!(auction.current_price, 1_200_000);
+    assert!(auction.is_graduated);
+    assert!(!auction.is_halted);
+    assert_eq!(auction.registry_status, REGISTRY_STATUS_ACTIVE | REGISTRY_STATUS_GRADUATED);
+    assert_eq!(auction.total_raised, 2_100_000);
+    assert_eq!(auction.total_withdrawn, 1_000_000);
+    assert_eq!(auction.total_value_locked, 1_045_000);
+    assert_eq!(auction.last_slot, 281_045_002);
+    assert_eq!(projection.last_slot, 281_045_002);
+
+    let bidders: Vec<_> = projection.auction_bidders(&auction_address).collect();
+    assert_eq!(bidders.len(), 2);
+
+    let referred = projection
+        .bidder(&auction_address, &key("HN7cABqLq46Es1jh92dQQisAq662SmxELLLsHHe4YWrH"))
+        .unwrap();
+    assert_eq!(referred.amount, 1_100_000);
+    assert_eq!(referred.count, 1);
+    assert_eq!(referred.referral_fees, 55_000);
+}
+
+#[test]
+fn test_project_refunded_auction() {
+    let mut projection = Projection::new();
+    for transaction in transactions(GRADUATED_AUCTION)
+        .into_iter()
+        .chain(transactions(REFUNDED_AUCTION))
+    {
+        projection.apply_transaction(&transaction);
+    }
+    assert_eq!(projection.auctions.len(), 2);
+
+    let auction_address = key("FwGMV2dxYLh9FfRQjVjJJUp7XbDb8hC1oBaUiCxHgDcN");
+    let auction = projection.auction(&auction_address).unwrap();
+    assert!(!auction.is_graduated);
+    assert_eq!(auction.total_refunded, 1_000_000);
+    assert_eq!(auction.total_value_locked, 0);
+
+    // The bidder also bid in the graduated auction, positions are per auction
+    let bidder_address = key("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+    let bidder = projection.bidder(&auction_address, &bidder_address).unwrap();
+    assert_eq!(bidder.amount, 0);
+    assert_eq!(bidder.count, 1);
+    assert_eq!(bidder.refunded, 1_000_000);
+
+    let graduated = key("9nYCsGQnQhFoXbmEVm7Cdp9xqcVrJLfsK3Uzj7cS4ah3");
+    assert_eq!(projection.bidder(&graduated, &bidder_address).unwrap().amount, 1_000_000);
+}
+
+#[test]
+fn test_project_event_cpis() {
+    let mut projection = Projection::new();
+    for transaction in transactions(CPI_EVENTS) {
+        projection.apply_transaction(&transaction);
+    }
+
+    let auction_address = key("Gq3RBNZkxj5Tgez5XiB6Mw9yhnB2zxLHjfYv1SEKXe8N");
+    let auction = projection.auction(&auction_address).unwrap();
+    assert_eq!(auction.auction_id, 3);
+    assert_eq!(auction.current_supply, 1);
+    assert!(auction.is_graduated);
+    assert_eq!(auction.total_raised, 2_000_000);
+    assert_eq!(auction.total_withdrawn, 2_000_000);
+    assert_eq!(auction.total_value_locked, 0);
+    assert_eq!(auction.graduated_at, 1_760_008_093);
+
+    let bidder = projection
+        .bidder(&auction_address, &key("HN7cABqLq46Es1jh92dQQisAq662SmxELLLsHHe4YWrH"))
+        .unwrap();
+    assert_eq!(bidder.amount, 2_000_000);
+    assert_eq!(bidder.count, 1);
+}
+
+#[test]
+fn test_decode_bid_details() {
+    let events: Vec<_> = transactions(CPI_EVENTS)
+        .iter()
+        .flat_map(IndexedTransaction::events)
+        .collect();
+    assert!(events.iter().all(|event| event.version() == EVENT_VERSION));
+
+    let bid = events
+        .iter()
+        .find_map(|event| match event {
+            SuperpullEvent::BidPlaced(bid) => Some(bid),
+            _ => None,
+        })
+        .unwrap();
+    let merkle_tree = key("Ctm1xkRVw6r2p3kLhBHXXm4NnVP2sDEXpPXUQz53aF1U");
+    assert_eq!(bid.price, 2_000_000);
+    assert_eq!(bid.item_serial, 1);
+    assert_eq!(bid.leaf_index, 0);
+    assert_eq!(bid.asset_id, mpl_bubblegum::utils::get_asset_id(&merkle_tree, 0));
+    assert_eq!(bid.payer, bid.bidder);
+    assert_eq!(bid.slot, 281_060_233);
+    assert_eq!(bid.timestamp, 1_760_008_093);
+}
+
+#[test]
+fn test_decode_only_known_versions() {
+    let auction = Pubkey::new_unique();
+    let mut data = price_update(auction, 1, 0).data();
+    assert!(SuperpullEvent::decode(&data).is_some());
+
+    // Payloads of an unknown schema version, or with trailing bytes, are
+    // not decoded
+    data[8] = EVENT_VERSION + 1;
+    assert!(SuperpullEvent::decode(&data).is_none());
+    data[8] = 0;
+    assert!(SuperpullEvent::decode(&data).is_none());
+    data[8] = EVENT_VERSION;
+    data.push(0);
+    assert!(SuperpullEvent::decode(&data).is_none());
+}
+
+#[test]
+fn test_decode_v1_recorded_transactions() {
+    for (v1, current) in [
+        (V1_GRADUATED_AUCTION, GRADUATED_AUCTION),
+        (V1_REFUNDED_AUCTION, REFUNDED_AUCTION),
+        (V1_CPI_EVENTS, CPI_EVENTS),
+    ] {
+        assert_eq!(event_names(v1), event_names(current));
+        let events: Vec<_> = transactions(v1).iter().flat_map(IndexedTransaction::events).collect();
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|event| event.version() == 1));
+    }
+
+    // Layouts unchanged in version 2 project the same state
+    let project = |fixtures: &[&str]| {
+        let mut projection = Projection::new();
+        for transaction in fixtures.iter().flat_map(|fixture| transactions(fixture)) {
+            projection.apply_transaction(&transaction);
+        }
+        projection
+    };
+    let v1 = project(&[V1_GRADUATED_AUCTION, V1_REFUNDED_AUCTION, V1_CPI_EVENTS]);
+    let current = project(&[GRADUATED_AUCTION, REFUNDED_AUCTION, CPI_EVENTS]);
+    assert_eq!(v1.auctions, current.auctions);
+    assert_eq!(v1.bidders, current.bidders);
+}
+
+#[test]
+fn test_decode_v1_item_refunded() {
+    let data = STANDARD.decode(V1_ITEM_REFUNDED).unwrap();
+    let Some(SuperpullEvent::ItemRefundedV1(event)) = SuperpullEvent::decode(&data) else {
+        panic!("expected a version 1 ItemRefunded");
+    };
+    let auction = key("FwGMV2dxYLh9FfRQjVjJJUp7XbDb8hC1oBaUiCxHgDcN");
+    let bidder = key("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+    assert_eq!(event.version, 1);
+    assert_eq!(event.auction, auction);
+    assert_eq!(event.bidder, bidder);
+    assert_eq!(event.item_serial, 1);
+    assert_eq!(event.amount, 1_000_000);
+    assert_eq!(event.slot, 281_310_554);
+
+    // The refunded amount stands for the item's whole contribution
+    let mut projection = Projection::new();
+    for transaction in transactions(REFUNDED_AUCTION).into_iter().take(2) {
+        projection.apply_transaction(&transaction);
+    }
+    let event = SuperpullEvent::decode(&data).unwrap();
+    assert_eq!(event.name(), "ItemRefunded");
+    projection.apply(281_310_554, &event);
+
+    let position = projection.bidder(&auction, &bidder).unwrap();
+    assert_eq!(position.amount, 0);
+    assert_eq!(position.count, 0);
+    assert_eq!(position.refunded, 1_000_000);
+    let auction = projection.auction(&auction).unwrap();
+    assert_eq!(auction.total_refunded, 1_000_000);
+    assert_eq!(auction.total_value_locked, 0);
+}
+
+#[test]
+fn test_decode_v1_item_refunded_with_price() {
+    // Builds before the version bump emitted the price and referral fee
+    // under version 1
+    let event = ItemRefunded {
+        version: 1,
+        auction: Pubkey::new_unique(),
+        bidder: Pubkey::new_unique(),
+        item_serial: 2,
+        asset_id: Pubkey::new_unique(),
+        amount: 950_000,
+        price: 1_000_000,
+        referral_fee: 50_000,
+        timestamp: 1_760_000_000,
+        slot: 281_310_554,
+    };
+    let Some(SuperpullEvent::ItemRefunded(decoded)) = SuperpullEvent::decode(&event.data()) else {
+        panic!("expected an ItemRefunded with its price");
+    };
+    assert_eq!(decoded.price, 1_000_000);
+    assert_eq!(decoded.referral_fee, 50_000);
+}
+
+#[test]
+fn test_project_status_changes() {
+    let auction = Pubkey::new_unique();
+    let event = SuperpullEvent::AuctionStatusChanged(AuctionStatusChanged {
+        version: EVENT_VERSION,
+        auction,
+        previous_status: REGISTRY_STATUS_ACTIVE,
+        status: REGISTRY_STATUS_HALTED,
+        timestamp: 1_760_000_000,
+        slot: 42,
+    });
+
+    let mut projection = Projection::new();
+    projection.apply(42, &event);
+    assert_eq!(projection.auction(&auction).unwrap().registry_status, REGISTRY_STATUS_HALTED);
+}
+
+#[test]
+fn test_project_item_refunds() {
+    let mut projection = Projection::new();
+    for transaction in transactions(GRADUATED_AUCTION)
+        .into_iter()
+        .chain(transactions(REFUNDED_AUCTION).into_iter().take(2))
+    {
+        projection.apply_transaction(&transaction);
+    }
+
+    let auction = key("FwGMV2dxYLh9FfRQjVjJJUp7XbDb8hC1oBaUiCxHgDcN");
+    let bidder = key("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+    let event = SuperpullEvent::ItemRefunded(ItemRefunded {
+        version: EVENT_VERSION,
+        auction,
+        bidder,
+        item_serial: 1,
+        asset_id: Pubkey::new_unique(),
+        amount: 1_000_000,
+        price: 1_000_000,
+        referral_fee: 50_000,
+        timestamp: 1_760_000_000,
+        slot: 281_310_554,
+    });
+    projection.apply(281_310_554, &event);
+
+    let position = projection.bidder(&auction, &bidder).unwrap();
+    assert_eq!(position.amount, 0);
+    assert_eq!(position.count, 0);
+    assert_eq!(position.referral_fees, 0);
+    assert_eq!(position.refunded, 1_000_000);
+    let auction = projection.auction(&auction).unwrap();
+    assert_eq!(auction.total_refunded, 1_000_000);
+    assert_eq!(auction.total_value_locked, 0);
+}
+
+#[test]
+fn test_project_item_sold_back() {
+    let mut projection = Projection::new();
+    for transaction in transactions(REFUNDED_AUCTION).into_iter().take(2) {
+        projection.apply_transaction(&transaction);
+    }
+
+    let auction = key("FwGMV2dxYLh9FfRQjVjJJUp7XbDb8hC1oBaUiCxHgDcN");
+    let bidder = key("4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T");
+    // Sold below its price with a 5% spread, only the bidder's amount leaves
+    // the TVL
+    let event = SuperpullEvent::ItemSoldBack(ItemSoldBack {
+        version: EVENT_VERSION,
+        auction,
+        bidder,
+        item_serial: 1,
+        asset_id: Pubkey::new_unique(),
+        price: 1_000_000,
+        referral_fee: 50_000,
+        sell_price: 600_000,
+        spread: 30_000,
+        amount: 570_000,
+        new_supply: 0,
+        timestamp: 1_760_000_000,
+        slot: 281_310_554,
+    });
+    projection.apply(281_310_554, &event);
+
+    let position = projection.bidder(&auction, &bidder).unwrap();
+    assert_eq!(position.amount, 0);
+    assert_eq!(position.count, 0);
+    assert_eq!(position.referral_fees, 0);
+    assert_eq!(position.refunded, 0);
+    let auction = projection.auction(&auction).unwrap();
+    assert_eq!(auction.current_supply, 0);
+    assert_eq!(auction.current_price, auction.base_price);
+    assert_eq!(auction.total_value_locked, 430_000);
+}
+
+#[test]
+fn test_decode_only_program_invocations() {
+    let auction = Pubkey::new_unique();
+    let other_program = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {other_program} invoke [1]"),
+        program_data(price_update(auction, 1, 0)),
+        format!("Program {} invoke [2]", superpull_program::ID),
+        program_data(price_update(auction, 2, 1)),
+        "Program data: bm90IGFuIGV2ZW50".to_string(),
+        format!("Program {} success", superpull_program::ID),
+        program_data(price_update(auction, 3, 2)),
+        format!("Program {other_program} success"),
+    ];
+
+    let events = decode_logs(&logs);
+    assert_eq!(events.len(), 1);
+    assert!(matches!(&events[0], SuperpullEvent::PriceUpdate(event) if event.price == 2));
+
+    // Another deployment of the program is decoded with its own id
+    let events = decode_program_logs(&other_program, &logs);
+    assert_eq!(events.len(), 2);
+}
+
+#[test]
+fn test_decode_only_signed_event_cpis() {
+    let auction = Pubkey::new_unique();
+    let (event_authority, _) =
+        Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &superpull_program::ID);
+
+    let mut spoofed_program = event_cpi(event_authority, price_update(auction, 1, 0));
+    spoofed_program.program_id = Pubkey::new_unique();
+    let instructions = vec![
+        spoofed_program,
+        event_cpi(Pubkey::new_unique(), price_update(auction, 2, 0)),
+        event_cpi(event_authority, price_update(auction, 3, 0)),
+    ];
+
+    let events = decode_inner_instructions(&instructions);
+    assert_eq!(events.len(), 1);
+    assert!(matches!(&events[0], SuperpullEvent::PriceUpdate(event) if event.price == 3));
+}
+
+#[test]
+fn test_events_of_unknown_auctions_are_tracked() {
+    let auction = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+    let transaction = IndexedTransaction {
+        slot: 42,
+        succeeded: true,
+        log_messages: vec![
+            format!("Program {} invoke [1]", superpull_program::ID),
+            program_data(BidRefunded {
+                version: EVENT_VERSION,
+                auction,
+                bidder,
+                amount: 500,
+                timestamp: 1_760_000_000,
+                slot: 42,
+            }),
+            format!("Program {} success", superpull_program::ID),
+        ],
+        inner_instructions: Vec::new(),
+    };
+
+    let mut projection = Projection::new();
+    assert_eq!(projection.apply_transaction(&transaction), 1);
+    assert_eq!(projection.auction(&auction).unwrap().total_refunded, 500);
+    assert_eq!(projection.bidder(&auction, &bidder).unwrap().refunded, 500);
+}
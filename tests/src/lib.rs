@@ -1,3 +1,46 @@
+#[cfg(test)]
+mod arbiter;
+#[cfg(test)]
+mod authority;
+#[cfg(test)]
+mod client;
+#[cfg(test)]
+mod config;
+#[cfg(test)]
+mod delivery;
+#[cfg(test)]
+mod fixtures;
+#[cfg(test)]
+mod gate;
+#[cfg(test)]
+mod indexer;
+#[cfg(test)]
+mod merkle;
+#[cfg(test)]
+mod milestones;
+#[cfg(test)]
+mod payees;
+#[cfg(test)]
+mod multisig;
+#[cfg(test)]
+mod phases;
+#[cfg(test)]
+mod receipts;
+#[cfg(test)]
+mod referrals;
+#[cfg(test)]
+mod refund_batch;
+#[cfg(test)]
+mod registry;
+#[cfg(test)]
+mod sell_back;
+#[cfg(test)]
+mod seller;
+#[cfg(test)]
+mod update_auction;
+#[cfg(test)]
+mod vesting;
+
 #[test]
 fn test_initialize() {
 }
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::keccak;
+use superpull_program::utils::merkle::{allowlist_leaf, verify_proof};
+
+fn hash_pair(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+    if a <= b {
+        keccak::hashv(&[&a, &b]).to_bytes()
+    } else {
+        keccak::hashv(&[&b, &a]).to_bytes()
+    }
+}
+
+#[test]
+fn test_allowlist_proof() {
+    let bidders: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let leaves: Vec<[u8; 32]> = bidders
+        .iter()
+        .enumerate()
+        .map(|(i, bidder)| allowlist_leaf(bidder, i as u64 + 1))
+        .collect();
+    let left = hash_pair(leaves[0], leaves[1]);
+    let right = hash_pair(leaves[2], leaves[3]);
+    let root = hash_pair(left, right);
+
+    assert!(verify_proof(&[leaves[1], right], root, leaves[0]));
+    assert!(verify_proof(&[leaves[2], left], root, leaves[3]));
+}
+
+#[test]
+fn test_allowlist_proof_rejects_wrong_allocation() {
+    let bidders: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+    let leaves: Vec<[u8; 32]> = bidders.iter().map(|bidder| allowlist_leaf(bidder, 1)).collect();
+    let root = hash_pair(leaves[0], leaves[1]);
+
+    let inflated = allowlist_leaf(&bidders[0], 10);
+    assert!(!verify_proof(&[leaves[1]], root, inflated));
+    assert!(!verify_proof(&[], root, leaves[0]));
+}
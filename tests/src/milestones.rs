@@ -0,0 +1,69 @@
+use anchor_lang::prelude::Pubkey;
+use superpull_program::state::{AuctionMilestones, AuctionState, Milestone};
+use crate::fixtures::auction_state;
+
+fn milestones(release_bps: &[u16], submitted_at: &[i64]) -> AuctionMilestones {
+    AuctionMilestones {
+        auction: Pubkey::new_unique(),
+        challenge_period: 100,
+        veto_threshold_bps: 5_000,
+        milestones: release_bps
+            .iter()
+            .enumerate()
+            .map(|(index, release_bps)| Milestone {
+                release_bps: *release_bps,
+                submitted_at: submitted_at.get(index).copied().unwrap_or_default(),
+                evidence_hash: [0; 32],
+                veto_weight: 0,
+            })
+            .collect(),
+        bump: 0,
+    }
+}
+
+#[test]
+fn test_released_bps_after_challenge_window() {
+    let milestones = milestones(&[2_000, 3_000, 5_000], &[1_000, 1_200]);
+    assert_eq!(milestones.next_index(), Some(2));
+    assert_eq!(milestones.released_bps(1_099).unwrap(), 0);
+    assert_eq!(milestones.released_bps(1_100).unwrap(), 2_000);
+    assert_eq!(milestones.released_bps(1_300).unwrap(), 5_000);
+}
+
+#[test]
+fn test_milestone_withdrawable_amount() {
+    let milestones = milestones(&[2_000, 8_000], &[1_000]);
+    let mut auction = AuctionState {
+        total_value_locked: 1_000,
+        is_graduated: true,
+        has_milestones: true,
+        ..auction_state()
+    };
+    assert_eq!(milestones.withdrawable_amount(&auction, 1_050).unwrap(), 0);
+    assert_eq!(milestones.withdrawable_amount(&auction, 1_100).unwrap(), 200);
+
+    auction.total_value_locked -= 200;
+    auction.total_withdrawn += 200;
+    assert_eq!(milestones.withdrawable_amount(&auction, 1_100).unwrap(), 0);
+}
+
+#[test]
+fn test_veto_threshold() {
+    let milestones = milestones(&[10_000], &[]);
+    assert!(!milestones.veto_passes(4, 10));
+    assert!(milestones.veto_passes(5, 10));
+    assert!(milestones.veto_passes(7, 10));
+}
+
+#[test]
+fn test_halted_refund_is_pro_rata() {
+    let auction = AuctionState {
+        is_halted: true,
+        refund_pool: 800,
+        refund_basis: 1_000,
+        ..auction_state()
+    };
+    assert_eq!(auction.halted_refund_amount(250).unwrap(), 200);
+    assert_eq!(auction.halted_refund_amount(3).unwrap(), 2);
+    assert_eq!(auction.halted_refund_amount(1_000).unwrap(), 800);
+}
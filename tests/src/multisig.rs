@@ -0,0 +1,102 @@
+use anchor_lang::{prelude::Pubkey, AnchorSerialize};
+use superpull_program::state::{
+    milestones_hash, phases_hash, validate_multisig, AuctionState, PhaseArgs, Proposal,
+    ProposalAction, UpdateAuctionArgs, MAX_MULTISIG_SIGNERS,
+};
+use crate::fixtures::auction_state;
+
+#[test]
+fn test_validate_multisig() {
+    let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    assert!(validate_multisig(&[], 0).is_ok());
+    assert!(validate_multisig(&signers, 2).is_ok());
+    assert!(validate_multisig(&signers, 3).is_ok());
+
+    assert!(validate_multisig(&[], 1).is_err());
+    assert!(validate_multisig(&signers, 0).is_err());
+    assert!(validate_multisig(&signers, 4).is_err());
+
+    let duplicated = vec![signers[0], signers[1], signers[0]];
+    assert!(validate_multisig(&duplicated, 2).is_err());
+
+    let too_many: Vec<Pubkey> = (0..=MAX_MULTISIG_SIGNERS).map(|_| Pubkey::new_unique()).collect();
+    assert!(validate_multisig(&too_many, 2).is_err());
+}
+
+#[test]
+fn test_proposal_approvals() {
+    let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let auction = AuctionState {
+        multisig_signers: signers.clone(),
+        multisig_threshold: 2,
+        ..auction_state()
+    };
+    assert!(auction.has_multisig());
+    assert_eq!(auction.multisig_signer_index(&signers[2]), Some(2));
+    assert_eq!(auction.multisig_signer_index(&Pubkey::new_unique()), None);
+
+    let mut proposal = Proposal {
+        auction: Pubkey::new_unique(),
+        index: 0,
+        proposer: signers[0],
+        action: ProposalAction::Cancel,
+        approvals: 1,
+        executed: false,
+        created_at: 0,
+        bump: 0,
+    };
+    assert!(proposal.has_approved(0));
+    assert!(!proposal.has_approved(2));
+    assert!(proposal.approval_count() < auction.multisig_threshold);
+
+    proposal.approvals |= 1 << 2;
+    assert_eq!(proposal.approval_count(), 2);
+}
+
+#[test]
+fn test_proposal_actions_fit_proposal() {
+    let actions = [
+        ProposalAction::Withdraw { amount: Some(1) },
+        ProposalAction::Cancel,
+        ProposalAction::Update {
+            args: UpdateAuctionArgs {
+                deadline: Some(1),
+                max_per_wallet: Some(1),
+                treasury: Some(Pubkey::new_unique()),
+            },
+        },
+        ProposalAction::ProposeAuthority { new_authority: Some(Pubkey::new_unique()) },
+        ProposalAction::SetPhases { phases_hash: [1; 32] },
+        ProposalAction::SetMilestones { milestones_hash: [1; 32] },
+        ProposalAction::SetSellBack { spread_bps: Some(500) },
+    ];
+    for action in actions {
+        assert!(action.try_to_vec().unwrap().len() <= ProposalAction::LEN);
+    }
+}
+
+#[test]
+fn test_proposal_hashes_bind_arguments() {
+    let phase = PhaseArgs {
+        start_time: 0,
+        end_time: 100,
+        base_price: 10,
+        price_increment: 2,
+        max_per_wallet: 1,
+        supply: 5,
+        merkle_root: None,
+    };
+    let approved = phases_hash(&[phase]).unwrap();
+    assert_eq!(phases_hash(&[phase]).unwrap(), approved);
+    assert_ne!(
+        phases_hash(&[PhaseArgs { base_price: 1, ..phase }]).unwrap(),
+        approved
+    );
+    assert_ne!(phases_hash(&[phase, phase]).unwrap(), approved);
+
+    let approved = milestones_hash(&[5_000, 5_000], 86_400, 5_000).unwrap();
+    assert_eq!(milestones_hash(&[5_000, 5_000], 86_400, 5_000).unwrap(), approved);
+    assert_ne!(milestones_hash(&[10_000], 86_400, 5_000).unwrap(), approved);
+    assert_ne!(milestones_hash(&[5_000, 5_000], 3_600, 5_000).unwrap(), approved);
+    assert_ne!(milestones_hash(&[5_000, 5_000], 86_400, 2_500).unwrap(), approved);
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::Pubkey;
+use superpull_program::state::{validate_payees, AuctionState, Payee};
+use crate::fixtures::auction_state;
+
+fn payees(shares: &[u16]) -> Vec<Payee> {
+    shares
+        .iter()
+        .map(|share_bps| Payee {
+            wallet: Pubkey::new_unique(),
+            share_bps: *share_bps,
+        })
+        .collect()
+}
+
+#[test]
+fn test_payee_amounts_assign_dust() {
+    let auction = AuctionState {
+        payees: payees(&[3_333, 3_333, 3_334]),
+        dust_payee_index: 2,
+        ..auction_state()
+    };
+
+    let amounts = auction.payee_amounts(100).unwrap();
+    assert_eq!(amounts, vec![33, 33, 34]);
+    assert_eq!(amounts.iter().sum::<u64>(), 100);
+
+    let amounts = auction.payee_amounts(10).unwrap();
+    assert_eq!(amounts, vec![3, 3, 4]);
+}
+
+#[test]
+fn test_payee_amounts_dust_to_designated_payee() {
+    let auction = AuctionState {
+        payees: payees(&[5_000, 2_500, 2_500]),
+        dust_payee_index: 0,
+        ..auction_state()
+    };
+
+    assert_eq!(auction.payee_amounts(7).unwrap(), vec![5, 1, 1]);
+}
+
+#[test]
+fn test_validate_payees() {
+    assert!(validate_payees(&[], 0).is_ok());
+    assert!(validate_payees(&payees(&[6_000, 4_000]), 1).is_ok());
+
+    // Shares must sum to 100%
+    assert!(validate_payees(&payees(&[6_000, 3_000]), 0).is_err());
+    // Dust payee must exist
+    assert!(validate_payees(&payees(&[6_000, 4_000]), 2).is_err());
+    // Shares must be non-zero
+    assert!(validate_payees(&payees(&[10_000, 0]), 0).is_err());
+    // At most five payees
+    assert!(validate_payees(&payees(&[2_000, 2_000, 2_000, 2_000, 1_000, 1_000]), 0).is_err());
+}
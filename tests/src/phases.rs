@@ -0,0 +1,53 @@
+use anchor_lang::prelude::Pubkey;
+use superpull_program::state::{AuctionPhases, BidState, Phase, PhaseArgs};
+use crate::fixtures::bid_state;
+
+fn phase(start_time: i64, end_time: i64) -> Phase {
+    Phase::from(PhaseArgs {
+        start_time,
+        end_time,
+        base_price: 10,
+        price_increment: 2,
+        max_per_wallet: 1,
+        supply: 5,
+        merkle_root: None,
+    })
+}
+
+#[test]
+fn test_active_phase_index() {
+    let phases = AuctionPhases {
+        auction: Pubkey::new_unique(),
+        phases: vec![phase(100, 200), phase(200, 300), phase(400, 500)],
+        bump: 255,
+    };
+
+    assert_eq!(phases.active_phase_index(99), None);
+    assert_eq!(phases.active_phase_index(100), Some(0));
+    assert_eq!(phases.active_phase_index(200), Some(1));
+    assert_eq!(phases.active_phase_index(350), None);
+    assert_eq!(phases.active_phase_index(499), Some(2));
+    assert_eq!(phases.active_phase_index(500), None);
+}
+
+#[test]
+fn test_phase_price_follows_phase_supply() {
+    let mut phase = phase(100, 200);
+    assert_eq!(phase.current_price().unwrap(), 10);
+
+    phase.minted = 3;
+    assert_eq!(phase.current_price().unwrap(), 16);
+}
+
+#[test]
+fn test_phase_count_resets_between_phases() {
+    let bid = BidState {
+        count: 3,
+        phase_index: 1,
+        phase_count: 2,
+        ..bid_state()
+    };
+
+    assert_eq!(bid.phase_count_for(1), 2);
+    assert_eq!(bid.phase_count_for(2), 0);
+}
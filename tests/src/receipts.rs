@@ -0,0 +1,76 @@
+use anchor_lang::prelude::{AnchorSerialize, Pubkey};
+use superpull_client::pda;
+use superpull_program::{
+    instructions::release_refund,
+    state::{AuctionState, ItemReceipt},
+};
+use crate::fixtures::auction_state;
+
+#[test]
+fn test_item_receipt_len() {
+    let receipt = ItemReceipt {
+        auction: Pubkey::new_unique(),
+        serial: 1,
+        bidder: Pubkey::new_unique(),
+        price: 1_000_000,
+        referral_fee: 50_000,
+        timestamp: 1_760_000_000,
+        leaf_index: 0,
+        asset_id: mpl_bubblegum::utils::get_asset_id(&Pubkey::new_unique(), 0),
+        bump: 255,
+        referrer: Some(Pubkey::new_unique()),
+    };
+    assert_eq!(receipt.try_to_vec().unwrap().len() + 8, ItemReceipt::LEN);
+}
+
+#[test]
+fn test_item_serials_start_at_one() {
+    let mut auction = auction_state();
+    assert_eq!(auction.next_item_serial(), 1);
+    auction.current_supply = 9;
+    assert_eq!(auction.next_item_serial(), 10);
+
+    // Each serial has its own receipt
+    let address = Pubkey::new_unique();
+    assert_ne!(pda::find_item_receipt(&address, 1).0, pda::find_item_receipt(&address, 2).0);
+}
+
+#[test]
+fn test_item_refunds_match_full_refund() {
+    // Two items, the second referred with a 10% referral fee
+    let items = [(100, 0), (110, 11)];
+    let auction = AuctionState {
+        total_value_locked: 199,
+        referral_fees_owed: 11,
+        ..auction_state()
+    };
+
+    let mut full = auction.clone();
+    assert_eq!(release_refund(&mut full, 210, 11).unwrap(), 210);
+
+    let mut by_item = auction;
+    for (price, referral_fee) in items {
+        assert_eq!(release_refund(&mut by_item, price, referral_fee).unwrap(), price);
+    }
+    assert_eq!(by_item.total_value_locked, 0);
+    assert_eq!(by_item.referral_fees_owed, 0);
+    assert_eq!(by_item.total_value_locked, full.total_value_locked);
+    assert_eq!(by_item.referral_fees_owed, full.referral_fees_owed);
+}
+
+#[test]
+fn test_halted_item_refund_is_pro_rata() {
+    let mut auction = AuctionState {
+        is_halted: true,
+        total_value_locked: 800,
+        referral_fees_owed: 11,
+        refund_pool: 800,
+        refund_basis: 1_000,
+        ..auction_state()
+    };
+    // The share of the item's price net of its referral fee, the fee stays
+    // owed to the referrer
+    assert_eq!(release_refund(&mut auction, 110, 11).unwrap(), 79);
+    assert_eq!(auction.total_value_locked, 721);
+    assert_eq!(auction.referral_fees_owed, 11);
+}
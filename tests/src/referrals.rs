@@ -0,0 +1,42 @@
+use superpull_program::state::{fee_amount, ReferrerState};
+use crate::fixtures::referrer_state;
+
+#[test]
+fn test_referral_fee_rounds_down() {
+    assert_eq!(fee_amount(1_000, 500), Some(50));
+    assert_eq!(fee_amount(19, 500), Some(0));
+    assert_eq!(fee_amount(1_000, 0), Some(0));
+}
+
+#[test]
+fn test_claimable_rewards() {
+    let mut referrer = ReferrerState {
+        total_rewards: 120,
+        ..referrer_state()
+    };
+    assert_eq!(referrer.claimable_rewards(), 120);
+
+    referrer.claimed_rewards = 120;
+    assert_eq!(referrer.claimable_rewards(), 0);
+
+    referrer.total_rewards = 200;
+    assert_eq!(referrer.claimable_rewards(), 80);
+}
+
+#[test]
+fn test_released_item_leaves_referrer_totals() {
+    let mut referrer = ReferrerState {
+        referral_count: 2,
+        total_volume: 210,
+        total_rewards: 21,
+        ..referrer_state()
+    };
+    referrer.release_item(110, 11).unwrap();
+    assert_eq!(referrer.referral_count, 1);
+    assert_eq!(referrer.total_volume, 100);
+    assert_eq!(referrer.total_rewards, 10);
+    assert_eq!(referrer.claimable_rewards(), 10);
+
+    // An item the referrer never got credited for cannot be released
+    assert!(referrer.release_item(110, 11).is_err());
+}
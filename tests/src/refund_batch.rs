@@ -0,0 +1,88 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::{associated_token::get_associated_token_address_with_program_id, token::spl_token};
+use superpull_client::pda;
+use superpull_program::{
+    instructions::{check_refund_bid, close_receipts, is_refund_token_account},
+    state::BidState,
+    utils::errors::SuperpullProgramError,
+};
+use crate::fixtures::{bid_state, token_account_data, TestAccount};
+
+fn bid(auction: Pubkey, bidder: Pubkey) -> BidState {
+    BidState {
+        auction,
+        bidder,
+        amount: 100,
+        count: 1,
+        ..bid_state()
+    }
+}
+
+#[test]
+fn test_refund_bid_matches_auction_and_bidder() {
+    let auction = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+    let bid = bid(auction, bidder);
+    assert!(check_refund_bid(&auction, &bid, &bidder).is_ok());
+
+    // Bid of another auction
+    assert_eq!(
+        check_refund_bid(&Pubkey::new_unique(), &bid, &bidder).unwrap_err(),
+        SuperpullProgramError::InvalidRefundAccounts.into()
+    );
+
+    // Rent must go back to the bidder
+    assert_eq!(
+        check_refund_bid(&auction, &bid, &Pubkey::new_unique()).unwrap_err(),
+        SuperpullProgramError::InvalidRefundAccounts.into()
+    );
+}
+
+#[test]
+fn test_refund_token_account_is_bidder_ata() {
+    let mint = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+    let ata = pda::token_account(&bidder, &mint);
+    let mut account = TestAccount::new(ata, spl_token::ID, token_account_data(&bidder, &mint, 0));
+    assert!(is_refund_token_account(&mint, &bidder, &account.info()));
+
+    // Missing, not yet created by the bidder
+    let mut missing = TestAccount::new(ata, Pubkey::default(), Vec::new());
+    assert!(!is_refund_token_account(&mint, &bidder, &missing.info()));
+
+    // Token account of the bidder at another address
+    let mut other = TestAccount::new(
+        Pubkey::new_unique(),
+        spl_token::ID,
+        token_account_data(&bidder, &mint, 0),
+    );
+    assert!(!is_refund_token_account(&mint, &bidder, &other.info()));
+
+    // The associated token account is derived for the SPL token program
+    // only, a token account of another program does not qualify
+    let foreign_program = Pubkey::new_unique();
+    let mut foreign = TestAccount::new(
+        get_associated_token_address_with_program_id(&bidder, &mint, &foreign_program),
+        foreign_program,
+        token_account_data(&bidder, &mint, 0),
+    );
+    assert!(!is_refund_token_account(&mint, &bidder, &foreign.info()));
+}
+
+#[test]
+fn test_close_receipts_requires_every_open_receipt() {
+    let auction = Pubkey::new_unique();
+    let bidder = Pubkey::new_unique();
+    let mut bid = BidState {
+        open_receipts: 2,
+        ..bid(auction, bidder)
+    };
+    let mut wallet = TestAccount::new(bidder, Pubkey::default(), Vec::new());
+
+    // A receipt left open could still be refunded with refund_item
+    assert_eq!(
+        close_receipts(&auction, &mut bid, &wallet.info(), &[]).unwrap_err(),
+        SuperpullProgramError::OpenItemReceipts.into()
+    );
+    assert_eq!(bid.open_receipts, 2);
+}
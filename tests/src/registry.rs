@@ -0,0 +1,99 @@
+use anchor_lang::prelude::{AnchorSerialize, Pubkey};
+use superpull_program::state::{
+    registry_location, AuctionState, Registry, RegistryEntry, RegistryPage, REGISTRY_PAGE_SIZE,
+    REGISTRY_STATUS_ACTIVE, REGISTRY_STATUS_EXPIRED, REGISTRY_STATUS_GRADUATED,
+    REGISTRY_STATUS_HALTED, REGISTRY_STATUS_SOLD_OUT,
+};
+use crate::fixtures::auction_state;
+
+fn open_auction() -> AuctionState {
+    AuctionState {
+        max_supply: 10,
+        minimum_items: 5,
+        deadline: 1_000,
+        ..auction_state()
+    }
+}
+
+#[test]
+fn test_registry_pages_fill_in_order() {
+    assert_eq!(registry_location(0), (0, 0));
+    assert_eq!(registry_location(REGISTRY_PAGE_SIZE as u64 - 1), (0, REGISTRY_PAGE_SIZE - 1));
+    assert_eq!(registry_location(REGISTRY_PAGE_SIZE as u64), (1, 0));
+
+    let registry = Registry {
+        authority: Pubkey::default(),
+        auction_count: 2 * REGISTRY_PAGE_SIZE as u64 + 3,
+        bump: 0,
+    };
+    assert_eq!(registry.current_page(), 2);
+}
+
+#[test]
+fn test_full_registry_page_fits_account() {
+    let page = RegistryPage {
+        registry: Pubkey::new_unique(),
+        index: 0,
+        entries: vec![
+            RegistryEntry {
+                auction: Pubkey::new_unique(),
+                status: REGISTRY_STATUS_ACTIVE,
+            };
+            REGISTRY_PAGE_SIZE
+        ],
+        bump: 255,
+    };
+    assert_eq!(page.try_to_vec().unwrap().len() + 8, RegistryPage::LEN);
+}
+
+#[test]
+fn test_registry_status_of_open_auction() {
+    let auction = open_auction();
+    assert_eq!(auction.registry_status(1_000), REGISTRY_STATUS_ACTIVE);
+    assert_eq!(auction.registry_status(1_001), REGISTRY_STATUS_EXPIRED);
+}
+
+#[test]
+fn test_registry_status_of_graduated_auction() {
+    let auction = AuctionState {
+        current_supply: 5,
+        is_graduated: true,
+        ..open_auction()
+    };
+    // Graduated auctions keep selling after the deadline
+    assert_eq!(
+        auction.registry_status(2_000),
+        REGISTRY_STATUS_ACTIVE | REGISTRY_STATUS_GRADUATED
+    );
+
+    let auction = AuctionState {
+        current_supply: 10,
+        ..auction
+    };
+    assert_eq!(
+        auction.registry_status(2_000),
+        REGISTRY_STATUS_GRADUATED | REGISTRY_STATUS_SOLD_OUT
+    );
+}
+
+#[test]
+fn test_registry_status_of_halted_auction() {
+    let auction = AuctionState {
+        is_halted: true,
+        ..open_auction()
+    };
+    assert_eq!(auction.registry_status(500), REGISTRY_STATUS_HALTED);
+
+    // Missing the delivery deadline makes the auction refundable as well
+    let auction = AuctionState {
+        current_supply: 5,
+        is_graduated: true,
+        graduated_at: 500,
+        delivery_period: Some(100),
+        ..open_auction()
+    };
+    assert_eq!(
+        auction.registry_status(601),
+        REGISTRY_STATUS_GRADUATED | REGISTRY_STATUS_HALTED
+    );
+}
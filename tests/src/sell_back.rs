@@ -0,0 +1,127 @@
+use anchor_lang::prelude::AnchorSerialize;
+use superpull_program::{
+    instructions::{release_refund, release_sell_back},
+    state::{fee_amount, AuctionState},
+};
+use crate::fixtures::auction_state;
+
+/// Auction with items bought at `prices` on a 100 + 10 per item curve, the
+/// escrow holds the sum of the prices
+fn auction_with_items(items: &[(u64, u64)]) -> (AuctionState, u64) {
+    let auction = AuctionState {
+        base_price: 100,
+        price_increment: 10,
+        current_supply: items.len() as u64,
+        total_value_locked: items.iter().map(|(price, fee)| price - fee).sum(),
+        referral_fees_owed: items.iter().map(|(_, fee)| fee).sum(),
+        ..auction_state()
+    };
+    let escrow = items.iter().map(|(price, _)| price).sum();
+    (auction, escrow)
+}
+
+#[test]
+fn test_auction_len_covers_sell_back_fields() {
+    let auction = AuctionState {
+        sell_back_spread_bps: Some(500),
+        last_item_serial: 9,
+        ..auction_state()
+    };
+    assert!(auction.try_to_vec().unwrap().len() + 8 <= AuctionState::LEN);
+}
+
+#[test]
+fn test_serials_continue_after_sell_back() {
+    // Items 1 to 3 were sold and one of them was sold back
+    let auction = AuctionState {
+        current_supply: 2,
+        last_item_serial: 3,
+        ..auction_state()
+    };
+    assert_eq!(auction.next_item_serial(), 4);
+
+    // Auctions created before sell-backs only track the supply
+    let legacy = AuctionState {
+        current_supply: 3,
+        ..auction_state()
+    };
+    assert_eq!(legacy.next_item_serial(), 4);
+}
+
+#[test]
+fn test_sell_back_price_follows_curve() {
+    let auction = AuctionState {
+        base_price: 100,
+        price_increment: 10,
+        current_supply: 5,
+        ..auction_state()
+    };
+    // The last item sold went for 140, the curve price once it is sold back
+    assert_eq!(auction.current_price().unwrap(), 150);
+    assert_eq!(auction.sell_back_price(140).unwrap(), 140);
+
+    // An early item is capped at its price so the other bids stay covered
+    assert_eq!(auction.sell_back_price(100).unwrap(), 100);
+
+    // The spread is taken from the sell price
+    assert_eq!(fee_amount(auction.sell_back_price(140).unwrap(), 500), Some(7));
+}
+
+#[test]
+fn test_sell_back_releases_whole_item() {
+    // The referred item sold for 110 with a 25% referral fee
+    let (mut auction, escrow) = auction_with_items(&[(100, 0), (110, 27), (120, 0)]);
+    let split = release_sell_back(&mut auction, 110, 27, 500).unwrap();
+    assert_eq!(split.sell_price, 110);
+    assert_eq!(split.spread, 5);
+    assert_eq!(split.amount, 105);
+    assert_eq!(split.retained, 5);
+
+    // Only the bidder's amount leaves the escrow, the spread and the fee no
+    // longer owed stay in the TVL
+    assert_eq!(auction.current_supply, 2);
+    assert_eq!(auction.total_value_locked, 225);
+    assert_eq!(auction.referral_fees_owed, 0);
+    assert_eq!(escrow - split.amount, 225);
+}
+
+#[test]
+fn test_sell_back_below_referral_fee() {
+    // A single item is left, the curve is back at its base price, below
+    // the referral fee of an item bought high
+    let (mut auction, _) = auction_with_items(&[(400, 100)]);
+    auction.base_price = 50;
+    let split = release_sell_back(&mut auction, 400, 100, 500).unwrap();
+    assert_eq!(split.sell_price, 50);
+    assert_eq!(split.spread, 2);
+    assert_eq!(split.amount, 48);
+    assert_eq!(split.retained, 352);
+    assert_eq!(auction.total_value_locked, 352);
+    assert_eq!(auction.referral_fees_owed, 0);
+}
+
+#[test]
+fn test_failed_auction_after_sell_back_keeps_retained_in_escrow() {
+    let items = [(100, 10), (110, 0), (120, 12), (130, 0)];
+    let (mut auction, mut escrow) = auction_with_items(&items);
+
+    // Once an earlier item is sold back the curve is below the price of the
+    // last one, so part of its price stays in the escrow
+    let mut retained = 0;
+    for (price, referral_fee) in [items[1], items[3]] {
+        let split = release_sell_back(&mut auction, price, referral_fee, 500).unwrap();
+        escrow -= split.amount;
+        retained += split.retained;
+    }
+    assert_eq!(auction.current_supply, 2);
+    assert_eq!(escrow, auction.total_value_locked + auction.referral_fees_owed);
+
+    // The auction fails, the remaining items are refunded in full and only
+    // what the sell-backs retained is left
+    for (price, referral_fee) in [items[0], items[2]] {
+        escrow -= release_refund(&mut auction, price, referral_fee).unwrap();
+    }
+    assert_eq!(escrow, retained);
+    assert_eq!(auction.total_value_locked, retained);
+    assert_eq!(auction.referral_fees_owed, 0);
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::Pubkey;
+use superpull_program::state::{AuctionState, SellerCounter};
+use crate::fixtures::auction_state;
+
+fn signer_address(auction: &AuctionState) -> Pubkey {
+    auction.with_signer_seeds(|seeds| {
+        Pubkey::create_program_address(seeds[0], &superpull_program::ID).unwrap()
+    })
+}
+
+#[test]
+fn test_seller_counter_ids_start_at_one() {
+    let mut counter = SellerCounter {
+        authority: Pubkey::new_unique(),
+        auction_count: 0,
+        bump: 0,
+    };
+    assert_eq!(counter.next_auction_id(), 1);
+
+    counter.auction_count = counter.next_auction_id();
+    assert_eq!(counter.next_auction_id(), 2);
+}
+
+#[test]
+fn test_auction_id_seeds_allow_several_auctions_per_collection() {
+    let authority = Pubkey::new_unique();
+    let collection_mint = Pubkey::new_unique();
+
+    let addresses: Vec<Pubkey> = (1u64..=2)
+        .map(|auction_id| {
+            let (address, bump) = Pubkey::find_program_address(
+                &[
+                    b"auction",
+                    authority.as_ref(),
+                    collection_mint.as_ref(),
+                    &auction_id.to_le_bytes(),
+                ],
+                &superpull_program::ID,
+            );
+            let auction = AuctionState {
+                authority,
+                creator: authority,
+                collection_mint,
+                bump,
+                auction_id,
+                ..auction_state()
+            };
+            assert_eq!(signer_address(&auction), address);
+            address
+        })
+        .collect();
+
+    assert_ne!(addresses[0], addresses[1]);
+}
+
+#[test]
+fn test_legacy_auction_without_id_keeps_address() {
+    let authority = Pubkey::new_unique();
+    let collection_mint = Pubkey::new_unique();
+    let (address, bump) = Pubkey::find_program_address(
+        &[b"auction", authority.as_ref(), collection_mint.as_ref()],
+        &superpull_program::ID,
+    );
+
+    // Auctions created before ids existed decode with id 0
+    let auction = AuctionState {
+        authority,
+        creator: authority,
+        collection_mint,
+        bump,
+        ..auction_state()
+    };
+    assert!(auction.auction_id_seed().is_empty());
+    assert_eq!(signer_address(&auction), address);
+}
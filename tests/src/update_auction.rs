@@ -0,0 +1,51 @@
+use superpull_program::{
+    instructions::check_deadline_extension,
+    state::AuctionState,
+    utils::errors::SuperpullProgramError,
+};
+use crate::fixtures::auction_state;
+
+#[test]
+fn test_deadline_extends_before_first_sale() {
+    let auction = AuctionState {
+        deadline: 1_000,
+        ..auction_state()
+    };
+
+    assert!(check_deadline_extension(&auction, 2_000, 900).is_ok());
+    assert!(check_deadline_extension(&auction, 2_000, 1_000).is_ok());
+
+    // Only extensions, and only before the deadline passes
+    assert_eq!(
+        check_deadline_extension(&auction, 1_000, 900).unwrap_err(),
+        SuperpullProgramError::InvalidDeadline.into()
+    );
+    assert_eq!(
+        check_deadline_extension(&auction, 2_000, 1_001).unwrap_err(),
+        SuperpullProgramError::InvalidDeadline.into()
+    );
+}
+
+#[test]
+fn test_deadline_locked_after_first_sale() {
+    let sold = AuctionState {
+        deadline: 1_000,
+        current_supply: 1,
+        last_item_serial: 1,
+        ..auction_state()
+    };
+    assert_eq!(
+        check_deadline_extension(&sold, 2_000, 900).unwrap_err(),
+        SuperpullProgramError::DeadlineLocked.into()
+    );
+
+    // Items sold back or refunded still lock the deadline
+    let sold_back = AuctionState {
+        current_supply: 0,
+        ..sold
+    };
+    assert_eq!(
+        check_deadline_extension(&sold_back, 2_000, 900).unwrap_err(),
+        SuperpullProgramError::DeadlineLocked.into()
+    );
+}
@@ -0,0 +1,54 @@
+use superpull_program::state::{AuctionState, VestingSchedule};
+use crate::fixtures::auction_state;
+
+fn graduated_auction(vesting: Option<VestingSchedule>) -> AuctionState {
+    AuctionState {
+        total_value_locked: 1_000,
+        is_graduated: true,
+        graduated_at: 100,
+        vesting,
+        ..auction_state()
+    }
+}
+
+#[test]
+fn test_vesting_cliff_and_linear_release() {
+    let vesting = VestingSchedule { cliff: 10, duration: 40 };
+    assert_eq!(vesting.vested_amount(1_000, 0), Some(0));
+    assert_eq!(vesting.vested_amount(1_000, 9), Some(0));
+    assert_eq!(vesting.vested_amount(1_000, 10), Some(250));
+    assert_eq!(vesting.vested_amount(1_000, 30), Some(750));
+    assert_eq!(vesting.vested_amount(1_000, 40), Some(1_000));
+    assert_eq!(vesting.vested_amount(1_000, 400), Some(1_000));
+    assert_eq!(vesting.vested_amount(u64::MAX, 20), Some(u64::MAX / 2));
+}
+
+#[test]
+fn test_withdrawable_without_vesting() {
+    let auction = graduated_auction(None);
+    assert_eq!(auction.withdrawable_amount(100).unwrap(), 1_000);
+
+    let auction = AuctionState {
+        is_graduated: false,
+        ..graduated_auction(None)
+    };
+    assert_eq!(auction.withdrawable_amount(100).unwrap(), 0);
+}
+
+#[test]
+fn test_withdrawable_tracks_partial_withdrawals() {
+    let mut auction = graduated_auction(Some(VestingSchedule { cliff: 0, duration: 100 }));
+    assert_eq!(auction.withdrawable_amount(150).unwrap(), 500);
+
+    // Withdraw 300 of the unlocked 500
+    auction.total_value_locked -= 300;
+    auction.total_withdrawn += 300;
+    assert_eq!(auction.total_proceeds().unwrap(), 1_000);
+    assert_eq!(auction.withdrawable_amount(150).unwrap(), 200);
+    assert_eq!(auction.withdrawable_amount(200).unwrap(), 700);
+
+    // Bids after graduation vest on the same schedule
+    auction.total_value_locked += 1_000;
+    assert_eq!(auction.vested_amount(150).unwrap(), 1_000);
+    assert_eq!(auction.withdrawable_amount(150).unwrap(), 700);
+}
@@ -66,17 +66,22 @@ describe("Superpull Program - Withdraw Flow", () => {
     await logAuctionState(ctx, "Before Withdrawal");
 
     const accounts = {
-      auction: ctx.auctionPda,
+      funds: {
+        auction: ctx.auctionPda,
+        milestones: null,
+        authorityTokenAccount: authorityTokenAccount,
+        treasuryTokenAccount: ctx.treasuryTokenAccount,
+        auctionTokenAccount: ctx.auctionTokenAccount,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      },
       authority: ctx.auctionCreator.publicKey,
-      authorityTokenAccount: authorityTokenAccount,
-      auctionTokenAccount: ctx.auctionTokenAccount,
-      systemProgram: anchor.web3.SystemProgram.programId,
-      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
     };
 
     await ctx.program.methods
-      .withdraw()
+      .withdraw(null)
       .accounts(accounts)
+      .signers([ctx.auctionCreator])
       .rpc();
 
     // Log final states
@@ -127,18 +132,23 @@ describe("Superpull Program - Withdraw Flow", () => {
     // Attempt withdrawal (should fail)
     console.log("\n❌ Attempting withdrawal before graduation...");
     const accounts = {
-      auction: ctx.auctionPda,
+      funds: {
+        auction: ctx.auctionPda,
+        milestones: null,
+        authorityTokenAccount: authorityTokenAccount,
+        treasuryTokenAccount: ctx.treasuryTokenAccount,
+        auctionTokenAccount: ctx.auctionTokenAccount,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      },
       authority: ctx.auctionCreator.publicKey,
-      authorityTokenAccount: authorityTokenAccount,
-      auctionTokenAccount: ctx.auctionTokenAccount,
-      systemProgram: anchor.web3.SystemProgram.programId,
-      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
     };
 
     try {
       await ctx.program.methods
-        .withdraw()
+        .withdraw(null)
         .accounts(accounts)
+        .signers([ctx.auctionCreator])
         .rpc();
       assert.fail("Should not be able to withdraw before graduation");
     } catch (error) {
@@ -186,17 +196,21 @@ describe("Superpull Program - Withdraw Flow", () => {
     // Attempt withdrawal with fake authority (should fail)
     console.log("\n❌ Attempting withdrawal with fake authority...");
     const accounts = {
-      auction: ctx.auctionPda,
+      funds: {
+        auction: ctx.auctionPda,
+        milestones: null,
+        authorityTokenAccount: fakeAuthorityTokenAccount,
+        treasuryTokenAccount: ctx.treasuryTokenAccount,
+        auctionTokenAccount: ctx.auctionTokenAccount,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      },
       authority: fakeAuthority.publicKey,
-      authorityTokenAccount: fakeAuthorityTokenAccount,
-      auctionTokenAccount: ctx.auctionTokenAccount,
-      systemProgram: anchor.web3.SystemProgram.programId,
-      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
     };
 
     try {
       await ctx.program.methods
-        .withdraw()
+        .withdraw(null)
         .accounts(accounts)
         .signers([fakeAuthority])
         .rpc();